@@ -0,0 +1,183 @@
+/*
+ * C interface of snake-core, built with `--features ffi`.
+ * Generated by cbindgen from src/ffi/mod.rs, do not edit. Regenerate with
+ * `SNAKE_CORE_UPDATE_HEADER=1 cargo test --test ffi_c_program`.
+ */
+
+#ifndef SNAKE_CORE_H
+#define SNAKE_CORE_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+/* Opaque game handle. */
+typedef struct SnakeContext SnakeContext;
+
+#define SNAKE_MODE_ENDLESS 0
+
+#define SNAKE_MODE_TIME_LIMITED 1
+
+#define SNAKE_MODE_TARGET_LENGTH 2
+
+#define SNAKE_MODE_SURVIVAL 3
+
+#define SNAKE_DIRECTION_UP 0
+
+#define SNAKE_DIRECTION_DOWN 1
+
+#define SNAKE_DIRECTION_LEFT 2
+
+#define SNAKE_DIRECTION_RIGHT 3
+
+#define SNAKE_CELL_EMPTY 0
+
+#define SNAKE_CELL_BODY 1
+
+#define SNAKE_CELL_APPLE 2
+
+#define SNAKE_CELL_WALL 3
+
+/*
+ Outside the visibility radius and never seen.
+ */
+#define SNAKE_CELL_HIDDEN 4
+
+#define SNAKE_CELL_CREATURE 5
+
+/*
+ Set on cells outside the radius that show what was last seen there.
+ */
+#define SNAKE_CELL_REMEMBERED_FLAG 256
+
+#define SNAKE_METRIC_MANHATTAN 0
+
+#define SNAKE_METRIC_EUCLIDEAN 1
+
+typedef enum SnakeStatus {
+  SNAKE_STATUS_OK = 0,
+  SNAKE_STATUS_NULL_POINTER = 1,
+  SNAKE_STATUS_INVALID_ARGUMENT = 2,
+  SNAKE_STATUS_DIRECTION_REJECTED = 3,
+  SNAKE_STATUS_OUT_OF_BOUNDS = 4,
+  SNAKE_STATUS_BUFFER_TOO_SMALL = 5,
+  SNAKE_STATUS_ENCODE_FAILED = 6,
+  SNAKE_STATUS_PANICKED = 7,
+} SnakeStatus;
+
+typedef struct SnakeGameConfig {
+  uint32_t mode;
+  /*
+   Tick budget, target length or hazard interval, depending on mode.
+   */
+  uint64_t mode_parameter;
+  uint64_t tick_interval_milliseconds;
+  uint32_t lives;
+  uint32_t respawn_invulnerability_ticks;
+  /*
+   0 disables the shrinking arena.
+   */
+  uint64_t shrink_interval_ticks;
+  /*
+   0 disables the fog of war.
+   */
+  uint32_t visibility_radius;
+  uint32_t visibility_metric;
+  /*
+   Non-zero to use `seed`, otherwise the game is seeded randomly.
+   */
+  uint8_t use_seed;
+  uint64_t seed;
+} SnakeGameConfig;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/*
+ Fills `config` with the defaults used by `snake_game_new` when it gets a null config.
+
+ # Safety
+ `config` must be null or point to writable memory for one `SnakeGameConfig`.
+ */
+enum SnakeStatus snake_game_config_default(struct SnakeGameConfig *config);
+
+/*
+ Creates and starts a game, storing its handle in `game`. Release it with `snake_game_free`.
+
+ # Safety
+ `config` must be null or point to a valid `SnakeGameConfig`, `game` must be null
+ or point to writable memory for one pointer.
+ */
+enum SnakeStatus snake_game_new(const struct SnakeGameConfig *config, SnakeContext **game);
+
+/*
+ Advances the game by one tick. `game_over`, when not null, is set to 1 if the tick ended a game.
+
+ # Safety
+ `game` must be null or a handle from `snake_game_new`, `game_over` must be null
+ or point to one writable byte.
+ */
+enum SnakeStatus snake_game_tick(SnakeContext *game, uint8_t *game_over);
+
+/*
+ Requests a turn, one of the `SNAKE_DIRECTION_*` values.
+
+ # Safety
+ `game` must be null or a handle from `snake_game_new`.
+ */
+enum SnakeStatus snake_game_change_direction(SnakeContext *game, uint32_t direction);
+
+/*
+ Reports the plane size in cells.
+
+ # Safety
+ `game` must be null or a handle from `snake_game_new`, `width` and `height` must
+ be null or point to writable `uint32_t` values.
+ */
+enum SnakeStatus snake_game_dimensions(const SnakeContext *game, uint32_t *width, uint32_t *height);
+
+/*
+ Reads the cell at column `x` and row `y` as one of the `SNAKE_CELL_*` values, as far
+ as the fog of war reveals it.
+
+ # Safety
+ `game` must be null or a handle from `snake_game_new`, `cell` must be null or
+ point to a writable `uint32_t`.
+ */
+enum SnakeStatus snake_game_cell(const SnakeContext *game, uint32_t x, uint32_t y, uint32_t *cell);
+
+/*
+ Reports the score of the running game.
+
+ # Safety
+ `game` must be null or a handle from `snake_game_new`, `score` must be null or
+ point to a writable `uint32_t`.
+ */
+enum SnakeStatus snake_game_score(const SnakeContext *game, uint32_t *score);
+
+/*
+ Writes the binary encoded snapshot into `buffer`. `written` always receives the
+ encoded length, so calling with a null buffer queries the size to allocate.
+
+ # Safety
+ `game` must be null or a handle from `snake_game_new`, `buffer` must be null or
+ point to `capacity` writable bytes, `written` must be null or point to a writable `size_t`.
+ */
+enum SnakeStatus snake_game_snapshot(const SnakeContext *game,
+                                     uint8_t *buffer,
+                                     size_t capacity,
+                                     size_t *written);
+
+/*
+ Releases a game handle. Passing null is a no-op.
+
+ # Safety
+ `game` must be null or a handle from `snake_game_new` that was not freed yet.
+ */
+void snake_game_free(SnakeContext *game);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* SNAKE_CORE_H */
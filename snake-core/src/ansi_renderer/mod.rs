@@ -0,0 +1,136 @@
+use alloc::string::String;
+
+use crate::multi_snake::MultiSnakeContext;
+use crate::visibility::VisibleCell;
+use crate::{PlaneField, Point, SnakeContext};
+
+const RESET: &str = "\x1b[0m";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AnsiPalette {
+    pub empty: &'static str,
+    pub body: &'static str,
+    pub head: &'static str,
+    pub apple: &'static str,
+    pub wall: &'static str,
+    pub creature: &'static str,
+    pub remembered: &'static str,
+    pub hidden: &'static str,
+}
+
+impl Default for AnsiPalette {
+    fn default() -> Self {
+        Self {
+            empty: "\x1b[90m",
+            body: "\x1b[32m",
+            head: "\x1b[92m",
+            apple: "\x1b[91m",
+            wall: "\x1b[37m",
+            creature: "\x1b[95m",
+            remembered: "\x1b[2;90m",
+            hidden: "\x1b[0m",
+        }
+    }
+}
+
+pub fn render_ansi(game: &SnakeContext, palette: &AnsiPalette) -> String {
+    let head = game.head();
+    let mut str_buff = String::new();
+    for y in 0..game.plane_height() {
+        for x in 0..game.plane_width() {
+            let point = Point(x, y);
+            let (color, glyph) = match game.visible_cell(point) {
+                VisibleCell::Visible(PlaneField::Body) if point == head => (palette.head, "██"),
+                VisibleCell::Visible(field) => (field_color(palette, field), glyph(field)),
+                VisibleCell::Remembered(field) => (palette.remembered, glyph(field)),
+                VisibleCell::Hidden => (palette.hidden, "  "),
+            };
+            str_buff.push_str(color);
+            str_buff.push_str(glyph);
+        }
+        str_buff.push_str(RESET);
+        str_buff.push('\n');
+    }
+    str_buff
+}
+
+// Each snake is drawn with its own palette, the rest of the board with the first one.
+pub fn render_ansi_players(game: &MultiSnakeContext, palettes: &[AnsiPalette]) -> String {
+    let board_palette = &palettes[0];
+    let mut str_buff = String::new();
+    for y in 0..game.plane_height() {
+        for x in 0..game.plane_width() {
+            let point = Point(x, y);
+            let (color, glyph) = match game.snake_at(point) {
+                Some(index) if game.snake(index).head() == Some(point) => {
+                    (palettes[index % palettes.len()].head, "██")
+                }
+                Some(index) => (palettes[index % palettes.len()].body, "██"),
+                None if game.is_wall(point) => (board_palette.wall, glyph(PlaneField::Wall)),
+                None if game.is_apple(point) => (board_palette.apple, glyph(PlaneField::Apple)),
+                None => (board_palette.empty, glyph(PlaneField::Empty)),
+            };
+            str_buff.push_str(color);
+            str_buff.push_str(glyph);
+        }
+        str_buff.push_str(RESET);
+        str_buff.push('\n');
+    }
+    str_buff
+}
+
+fn field_color(palette: &AnsiPalette, field: PlaneField) -> &'static str {
+    match field {
+        PlaneField::Body => palette.body,
+        PlaneField::Apple => palette.apple,
+        PlaneField::Wall => palette.wall,
+        PlaneField::Creature => palette.creature,
+        PlaneField::Empty => palette.empty,
+    }
+}
+
+fn glyph(field: PlaneField) -> &'static str {
+    match field {
+        PlaneField::Body => "██",
+        PlaneField::Apple => "● ",
+        PlaneField::Wall => "▒▒",
+        PlaneField::Creature => "@ ",
+        PlaneField::Empty => "· ",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_config::GameConfig;
+    use crate::multi_snake::MultiSnakeContext;
+    use crate::SnakeContext;
+
+    use super::{render_ansi, render_ansi_players, AnsiPalette};
+
+    #[test]
+    fn test_should_render_every_row_with_colored_two_column_cells() {
+        let mut game = SnakeContext::new();
+        game.new_game();
+        let palette = AnsiPalette::default();
+        let rendered = render_ansi(&game, &palette);
+        let rows = rendered.lines().collect::<Vec<&str>>();
+        assert_eq!(10, rows.len());
+        assert!(rows[5].contains(&format!("{}██{}██", palette.head, palette.body)));
+        assert!(rows[0].ends_with("\x1b[0m"));
+    }
+
+    #[test]
+    fn test_should_render_each_player_with_own_palette() {
+        let game = MultiSnakeContext::new(GameConfig::default(), 2);
+        let first = AnsiPalette::default();
+        let second = AnsiPalette {
+            head: "\x1b[94m",
+            ..AnsiPalette::default()
+        };
+        let rendered = render_ansi_players(&game, &[first, second]);
+        let rows = rendered.lines().collect::<Vec<&str>>();
+        assert_eq!(10, rows.len());
+        assert!(rows[3].contains(&format!("{}██{}██", first.head, first.body)));
+        assert!(rows[6].contains(&format!("{}██{}██", second.head, second.body)));
+    }
+}
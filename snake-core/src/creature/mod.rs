@@ -0,0 +1,155 @@
+use alloc::vec::Vec;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::game_config::board::BoardConfig;
+use crate::{Direction, Point};
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CreatureBehavior {
+    /// Walks back and forth, turning around after `range` steps or when blocked.
+    Patrol {
+        direction: Direction,
+        range: Option<u32>,
+    },
+    RandomWalk,
+    ChaseHead,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CreatureSpawning {
+    pub interval_ticks: u64,
+    pub behavior: CreatureBehavior,
+    pub max_creatures: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Creature {
+    pub position: Point,
+    pub behavior: CreatureBehavior,
+    heading: Direction,
+    steps_taken: u32,
+}
+
+impl Creature {
+    pub fn new(position: Point, behavior: CreatureBehavior) -> Self {
+        let heading = match behavior {
+            CreatureBehavior::Patrol { direction, .. } => direction,
+            _ => Direction::Left,
+        };
+        Self {
+            position,
+            behavior,
+            heading,
+            steps_taken: 0,
+        }
+    }
+
+    // Moves one cell, or stays in place when every wanted cell is blocked.
+    pub(crate) fn step<R: Rng, F: Fn(Point) -> bool>(
+        &mut self,
+        head: Point,
+        board: &BoardConfig,
+        rng: &mut R,
+        is_free: F,
+    ) {
+        let next = match self.behavior {
+            CreatureBehavior::Patrol { range, .. } => {
+                let leg_finished = range.is_some_and(|range| self.steps_taken >= range);
+                if leg_finished
+                    || !board
+                        .step(self.position, self.heading)
+                        .is_some_and(&is_free)
+                {
+                    self.heading = opposite(self.heading);
+                    self.steps_taken = 0;
+                }
+                board
+                    .step(self.position, self.heading)
+                    .filter(|&next| is_free(next))
+            }
+            CreatureBehavior::RandomWalk => DIRECTIONS
+                .iter()
+                .filter_map(|&direction| board.step(self.position, direction))
+                .filter(|&next| is_free(next))
+                .collect::<Vec<Point>>()
+                .choose(rng)
+                .copied(),
+            CreatureBehavior::ChaseHead => {
+                let current = board.distance(self.position, head);
+                DIRECTIONS
+                    .iter()
+                    .filter_map(|&direction| board.step(self.position, direction))
+                    .filter(|&next| is_free(next) && board.distance(next, head) < current)
+                    .min_by_key(|&next| board.distance(next, head))
+            }
+        };
+
+        if let Some(next) = next {
+            self.position = next;
+            self.steps_taken += 1;
+        }
+    }
+}
+
+fn opposite(direction: Direction) -> Direction {
+    match direction {
+        Direction::Up => Direction::Down,
+        Direction::Down => Direction::Up,
+        Direction::Left => Direction::Right,
+        Direction::Right => Direction::Left,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::game_config::board::BoardConfig;
+    use crate::{Direction, Point};
+
+    use super::{Creature, CreatureBehavior};
+
+    #[test]
+    fn test_should_turn_around_when_patrol_range_is_walked() {
+        let mut creature = Creature::new(
+            Point(5, 2),
+            CreatureBehavior::Patrol {
+                direction: Direction::Right,
+                range: Some(2),
+            },
+        );
+        let board = BoardConfig::default();
+        let mut rng = StdRng::seed_from_u64(0);
+        let positions = (0..4)
+            .map(|_| {
+                creature.step(Point(0, 0), &board, &mut rng, |_| true);
+                creature.position
+            })
+            .collect::<Vec<Point>>();
+        assert_eq!(
+            vec![Point(6, 2), Point(7, 2), Point(6, 2), Point(5, 2)],
+            positions
+        );
+    }
+
+    #[test]
+    fn test_should_step_towards_head_and_stay_when_blocked() {
+        let mut creature = Creature::new(Point(5, 5), CreatureBehavior::ChaseHead);
+        let board = BoardConfig::default();
+        let mut rng = StdRng::seed_from_u64(0);
+        creature.step(Point(5, 8), &board, &mut rng, |_| true);
+        assert_eq!(Point(5, 6), creature.position);
+        creature.step(Point(5, 8), &board, &mut rng, |point| point != Point(5, 7));
+        assert_eq!(Point(5, 6), creature.position);
+    }
+}
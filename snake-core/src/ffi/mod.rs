@@ -0,0 +1,407 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use crate::game_config::speed_policy::SpeedPolicy;
+use crate::game_config::{GameConfig, ShrinkingArenaConfig};
+use crate::game_mode::GameMode;
+use crate::visibility::{DistanceMetric, VisibilityConfig, VisibleCell};
+use crate::{Direction, PlaneField, Point, SnakeContext};
+
+pub const SNAKE_MODE_ENDLESS: u32 = 0;
+pub const SNAKE_MODE_TIME_LIMITED: u32 = 1;
+pub const SNAKE_MODE_TARGET_LENGTH: u32 = 2;
+pub const SNAKE_MODE_SURVIVAL: u32 = 3;
+
+pub const SNAKE_DIRECTION_UP: u32 = 0;
+pub const SNAKE_DIRECTION_DOWN: u32 = 1;
+pub const SNAKE_DIRECTION_LEFT: u32 = 2;
+pub const SNAKE_DIRECTION_RIGHT: u32 = 3;
+
+pub const SNAKE_CELL_EMPTY: u32 = 0;
+pub const SNAKE_CELL_BODY: u32 = 1;
+pub const SNAKE_CELL_APPLE: u32 = 2;
+pub const SNAKE_CELL_WALL: u32 = 3;
+/// Outside the visibility radius and never seen.
+pub const SNAKE_CELL_HIDDEN: u32 = 4;
+pub const SNAKE_CELL_CREATURE: u32 = 5;
+/// Set on cells outside the radius that show what was last seen there.
+pub const SNAKE_CELL_REMEMBERED_FLAG: u32 = 0x100;
+
+pub const SNAKE_METRIC_MANHATTAN: u32 = 0;
+pub const SNAKE_METRIC_EUCLIDEAN: u32 = 1;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnakeStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidArgument = 2,
+    DirectionRejected = 3,
+    OutOfBounds = 4,
+    BufferTooSmall = 5,
+    EncodeFailed = 6,
+    Panicked = 7,
+}
+
+// Plain integers instead of Rust enums, so out of range values coming from C
+// are rejected with an error code rather than being undefined behaviour.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SnakeGameConfig {
+    pub mode: u32,
+    /// Tick budget, target length or hazard interval, depending on mode.
+    pub mode_parameter: u64,
+    pub tick_interval_milliseconds: u64,
+    pub lives: u32,
+    pub respawn_invulnerability_ticks: u32,
+    /// 0 disables the shrinking arena.
+    pub shrink_interval_ticks: u64,
+    /// 0 disables the fog of war.
+    pub visibility_radius: u32,
+    pub visibility_metric: u32,
+    /// Non-zero to use `seed`, otherwise the game is seeded randomly.
+    pub use_seed: u8,
+    pub seed: u64,
+}
+
+impl Default for SnakeGameConfig {
+    fn default() -> Self {
+        let config = GameConfig::default();
+        Self {
+            mode: SNAKE_MODE_ENDLESS,
+            mode_parameter: 0,
+            tick_interval_milliseconds: config.speed_policy.interval_milliseconds(0, 0),
+            lives: config.lives,
+            respawn_invulnerability_ticks: config.respawn_invulnerability_ticks,
+            shrink_interval_ticks: 0,
+            visibility_radius: 0,
+            visibility_metric: SNAKE_METRIC_MANHATTAN,
+            use_seed: 0,
+            seed: 0,
+        }
+    }
+}
+
+impl TryFrom<&SnakeGameConfig> for GameConfig {
+    type Error = SnakeStatus;
+
+    fn try_from(config: &SnakeGameConfig) -> Result<Self, Self::Error> {
+        let parameter = config.mode_parameter;
+        let mode = match config.mode {
+            SNAKE_MODE_ENDLESS => GameMode::Endless,
+            SNAKE_MODE_TIME_LIMITED if parameter > 0 => GameMode::TimeLimited {
+                tick_budget: parameter,
+            },
+            SNAKE_MODE_TARGET_LENGTH if parameter > 0 => GameMode::TargetLength {
+                target_length: parameter as usize,
+            },
+            SNAKE_MODE_SURVIVAL if parameter > 0 => GameMode::Survival {
+                hazard_interval_ticks: parameter,
+            },
+            _ => return Err(SnakeStatus::InvalidArgument),
+        };
+        let metric = match config.visibility_metric {
+            SNAKE_METRIC_MANHATTAN => DistanceMetric::Manhattan,
+            SNAKE_METRIC_EUCLIDEAN => DistanceMetric::Euclidean,
+            _ => return Err(SnakeStatus::InvalidArgument),
+        };
+        if config.lives == 0 || config.tick_interval_milliseconds == 0 {
+            return Err(SnakeStatus::InvalidArgument);
+        }
+
+        Ok(GameConfig {
+            mode,
+            speed_policy: SpeedPolicy::Constant {
+                interval_milliseconds: config.tick_interval_milliseconds,
+            },
+            lives: config.lives,
+            respawn_invulnerability_ticks: config.respawn_invulnerability_ticks,
+            shrinking_arena: (config.shrink_interval_ticks > 0).then_some(ShrinkingArenaConfig {
+                shrink_interval_ticks: config.shrink_interval_ticks,
+            }),
+            level: None,
+            creature_spawning: None,
+            visibility: (config.visibility_radius > 0).then_some(VisibilityConfig {
+                radius: config.visibility_radius,
+                metric,
+            }),
+            seed: (config.use_seed != 0).then_some(config.seed),
+            ..GameConfig::default()
+        })
+    }
+}
+
+fn direction_from_code(code: u32) -> Option<Direction> {
+    match code {
+        SNAKE_DIRECTION_UP => Some(Direction::Up),
+        SNAKE_DIRECTION_DOWN => Some(Direction::Down),
+        SNAKE_DIRECTION_LEFT => Some(Direction::Left),
+        SNAKE_DIRECTION_RIGHT => Some(Direction::Right),
+        _ => None,
+    }
+}
+
+fn cell_code(field: PlaneField) -> u32 {
+    match field {
+        PlaneField::Empty => SNAKE_CELL_EMPTY,
+        PlaneField::Body => SNAKE_CELL_BODY,
+        PlaneField::Apple => SNAKE_CELL_APPLE,
+        PlaneField::Wall => SNAKE_CELL_WALL,
+        PlaneField::Creature => SNAKE_CELL_CREATURE,
+    }
+}
+
+// Unwinding into C is not allowed, so a panic becomes an error code.
+fn guarded<F: FnOnce() -> SnakeStatus>(body: F) -> SnakeStatus {
+    catch_unwind(AssertUnwindSafe(body)).unwrap_or(SnakeStatus::Panicked)
+}
+
+/// Fills `config` with the defaults used by `snake_game_new` when it gets a null config.
+///
+/// # Safety
+/// `config` must be null or point to writable memory for one `SnakeGameConfig`.
+#[no_mangle]
+pub unsafe extern "C" fn snake_game_config_default(config: *mut SnakeGameConfig) -> SnakeStatus {
+    if config.is_null() {
+        return SnakeStatus::NullPointer;
+    }
+    config.write(SnakeGameConfig::default());
+    SnakeStatus::Ok
+}
+
+/// Creates and starts a game, storing its handle in `game`. Release it with `snake_game_free`.
+///
+/// # Safety
+/// `config` must be null or point to a valid `SnakeGameConfig`, `game` must be null
+/// or point to writable memory for one pointer.
+#[no_mangle]
+pub unsafe extern "C" fn snake_game_new(
+    config: *const SnakeGameConfig,
+    game: *mut *mut SnakeContext,
+) -> SnakeStatus {
+    if game.is_null() {
+        return SnakeStatus::NullPointer;
+    }
+    let config = match config.as_ref() {
+        Some(config) => match GameConfig::try_from(config) {
+            Ok(config) => config,
+            Err(status) => return status,
+        },
+        None => GameConfig::default(),
+    };
+    guarded(|| {
+        let mut context = SnakeContext::with_config(config);
+        context.new_game();
+        game.write(Box::into_raw(Box::new(context)));
+        SnakeStatus::Ok
+    })
+}
+
+/// Advances the game by one tick. `game_over`, when not null, is set to 1 if the tick ended a game.
+///
+/// # Safety
+/// `game` must be null or a handle from `snake_game_new`, `game_over` must be null
+/// or point to one writable byte.
+#[no_mangle]
+pub unsafe extern "C" fn snake_game_tick(
+    game: *mut SnakeContext,
+    game_over: *mut u8,
+) -> SnakeStatus {
+    let game = match game.as_mut() {
+        Some(game) => game,
+        None => return SnakeStatus::NullPointer,
+    };
+    guarded(|| {
+        let result = game.update_position();
+        if !game_over.is_null() {
+            game_over.write(result.game_over.is_some() as u8);
+        }
+        SnakeStatus::Ok
+    })
+}
+
+/// Requests a turn, one of the `SNAKE_DIRECTION_*` values.
+///
+/// # Safety
+/// `game` must be null or a handle from `snake_game_new`.
+#[no_mangle]
+pub unsafe extern "C" fn snake_game_change_direction(
+    game: *mut SnakeContext,
+    direction: u32,
+) -> SnakeStatus {
+    let game = match game.as_mut() {
+        Some(game) => game,
+        None => return SnakeStatus::NullPointer,
+    };
+    let direction = match direction_from_code(direction) {
+        Some(direction) => direction,
+        None => return SnakeStatus::InvalidArgument,
+    };
+    guarded(|| match game.change_direction(direction) {
+        Ok(()) => SnakeStatus::Ok,
+        Err(_) => SnakeStatus::DirectionRejected,
+    })
+}
+
+/// Reports the plane size in cells.
+///
+/// # Safety
+/// `game` must be null or a handle from `snake_game_new`, `width` and `height` must
+/// be null or point to writable `uint32_t` values.
+#[no_mangle]
+pub unsafe extern "C" fn snake_game_dimensions(
+    game: *const SnakeContext,
+    width: *mut u32,
+    height: *mut u32,
+) -> SnakeStatus {
+    let game = match game.as_ref() {
+        Some(game) => game,
+        None => return SnakeStatus::NullPointer,
+    };
+    if width.is_null() || height.is_null() {
+        return SnakeStatus::NullPointer;
+    }
+    width.write(game.plane_width());
+    height.write(game.plane_height());
+    SnakeStatus::Ok
+}
+
+/// Reads the cell at column `x` and row `y` as one of the `SNAKE_CELL_*` values, as far
+/// as the fog of war reveals it.
+///
+/// # Safety
+/// `game` must be null or a handle from `snake_game_new`, `cell` must be null or
+/// point to a writable `uint32_t`.
+#[no_mangle]
+pub unsafe extern "C" fn snake_game_cell(
+    game: *const SnakeContext,
+    x: u32,
+    y: u32,
+    cell: *mut u32,
+) -> SnakeStatus {
+    let game = match game.as_ref() {
+        Some(game) => game,
+        None => return SnakeStatus::NullPointer,
+    };
+    if cell.is_null() {
+        return SnakeStatus::NullPointer;
+    }
+    if game.field_at(Point(x, y)).is_none() {
+        return SnakeStatus::OutOfBounds;
+    }
+    let code = match game.visible_cell(Point(x, y)) {
+        VisibleCell::Visible(field) => cell_code(field),
+        VisibleCell::Remembered(field) => cell_code(field) | SNAKE_CELL_REMEMBERED_FLAG,
+        VisibleCell::Hidden => SNAKE_CELL_HIDDEN,
+    };
+    cell.write(code);
+    SnakeStatus::Ok
+}
+
+/// Reports the score of the running game.
+///
+/// # Safety
+/// `game` must be null or a handle from `snake_game_new`, `score` must be null or
+/// point to a writable `uint32_t`.
+#[no_mangle]
+pub unsafe extern "C" fn snake_game_score(
+    game: *const SnakeContext,
+    score: *mut u32,
+) -> SnakeStatus {
+    let game = match game.as_ref() {
+        Some(game) => game,
+        None => return SnakeStatus::NullPointer,
+    };
+    if score.is_null() {
+        return SnakeStatus::NullPointer;
+    }
+    score.write(game.score());
+    SnakeStatus::Ok
+}
+
+/// Writes the binary encoded snapshot into `buffer`. `written` always receives the
+/// encoded length, so calling with a null buffer queries the size to allocate.
+///
+/// # Safety
+/// `game` must be null or a handle from `snake_game_new`, `buffer` must be null or
+/// point to `capacity` writable bytes, `written` must be null or point to a writable `size_t`.
+#[no_mangle]
+pub unsafe extern "C" fn snake_game_snapshot(
+    game: *const SnakeContext,
+    buffer: *mut u8,
+    capacity: usize,
+    written: *mut usize,
+) -> SnakeStatus {
+    let game = match game.as_ref() {
+        Some(game) => game,
+        None => return SnakeStatus::NullPointer,
+    };
+    if written.is_null() {
+        return SnakeStatus::NullPointer;
+    }
+    guarded(|| {
+        let bytes = match game.snapshot().to_bytes() {
+            Ok(bytes) => bytes,
+            Err(_) => return SnakeStatus::EncodeFailed,
+        };
+        written.write(bytes.len());
+        if buffer.is_null() || capacity < bytes.len() {
+            return SnakeStatus::BufferTooSmall;
+        }
+        ptr::copy_nonoverlapping(bytes.as_ptr(), buffer, bytes.len());
+        SnakeStatus::Ok
+    })
+}
+
+/// Releases a game handle. Passing null is a no-op.
+///
+/// # Safety
+/// `game` must be null or a handle from `snake_game_new` that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn snake_game_free(game: *mut SnakeContext) {
+    if !game.is_null() {
+        drop(Box::from_raw(game));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+
+    use super::*;
+
+    #[test]
+    fn test_should_report_error_codes_when_arguments_are_invalid() {
+        let mut config = SnakeGameConfig {
+            mode: 42,
+            ..SnakeGameConfig::default()
+        };
+        let mut game = ptr::null_mut();
+        unsafe {
+            assert_eq!(
+                SnakeStatus::InvalidArgument,
+                snake_game_new(&config, &mut game)
+            );
+            config.mode = SNAKE_MODE_ENDLESS;
+            assert_eq!(SnakeStatus::Ok, snake_game_new(&config, &mut game));
+            assert_eq!(
+                SnakeStatus::InvalidArgument,
+                snake_game_change_direction(game, 9)
+            );
+            assert_eq!(
+                SnakeStatus::DirectionRejected,
+                snake_game_change_direction(game, SNAKE_DIRECTION_RIGHT)
+            );
+            let mut cell = 0;
+            assert_eq!(
+                SnakeStatus::OutOfBounds,
+                snake_game_cell(game, 20, 0, &mut cell)
+            );
+            assert_eq!(
+                SnakeStatus::NullPointer,
+                snake_game_tick(ptr::null_mut(), ptr::null_mut())
+            );
+            snake_game_free(game);
+        }
+    }
+}
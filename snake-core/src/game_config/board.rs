@@ -0,0 +1,131 @@
+use crate::rule_set::wrap_around_step;
+use crate::{Direction, Point};
+
+pub const DEFAULT_BOARD_WIDTH: u32 = 20;
+pub const DEFAULT_BOARD_HEIGHT: u32 = 10;
+// Room for the starting snake and the free field in front of its head.
+pub const MIN_BOARD_WIDTH: u32 = 5;
+pub const MIN_BOARD_HEIGHT: u32 = 3;
+pub const MAX_BOARD_SIDE: u32 = 256;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Topology {
+    #[default]
+    Wrapping,
+    Walled,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BoardConfig {
+    pub width: u32,
+    pub height: u32,
+    pub topology: Topology,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoardConfigError {
+    TooSmall,
+    TooLarge,
+}
+
+impl Default for BoardConfig {
+    fn default() -> Self {
+        Self {
+            width: DEFAULT_BOARD_WIDTH,
+            height: DEFAULT_BOARD_HEIGHT,
+            topology: Topology::default(),
+        }
+    }
+}
+
+impl BoardConfig {
+    pub fn validate(&self) -> Result<(), BoardConfigError> {
+        if self.width < MIN_BOARD_WIDTH || self.height < MIN_BOARD_HEIGHT {
+            return Err(BoardConfigError::TooSmall);
+        }
+        if self.width > MAX_BOARD_SIDE || self.height > MAX_BOARD_SIDE {
+            return Err(BoardConfigError::TooLarge);
+        }
+        Ok(())
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.0 < self.width && point.1 < self.height
+    }
+
+    // On a walled board there is nothing past the edge.
+    pub fn step(&self, point: Point, direction: Direction) -> Option<Point> {
+        let Point(x, y) = point;
+        match self.topology {
+            Topology::Wrapping => Some(wrap_around_step(point, direction, self.width, self.height)),
+            Topology::Walled => match direction {
+                Direction::Up => y.checked_sub(1).map(|y| Point(x, y)),
+                Direction::Down => (y + 1 < self.height).then_some(Point(x, y + 1)),
+                Direction::Left => x.checked_sub(1).map(|x| Point(x, y)),
+                Direction::Right => (x + 1 < self.width).then_some(Point(x + 1, y)),
+            },
+        }
+    }
+
+    // Distances along each axis, measured across the edges on a wrapping board.
+    pub fn axis_distances(&self, from: Point, to: Point) -> (u32, u32) {
+        let dx = from.0.abs_diff(to.0);
+        let dy = from.1.abs_diff(to.1);
+        match self.topology {
+            Topology::Wrapping => (dx.min(self.width - dx), dy.min(self.height - dy)),
+            Topology::Walled => (dx, dy),
+        }
+    }
+
+    pub fn distance(&self, from: Point, to: Point) -> u32 {
+        let (dx, dy) = self.axis_distances(from, to);
+        dx + dy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Direction, Point};
+
+    use super::{BoardConfig, BoardConfigError, Topology};
+
+    #[test]
+    fn test_should_stop_at_the_edge_when_board_is_walled() {
+        let board = BoardConfig {
+            width: 8,
+            height: 6,
+            topology: Topology::Walled,
+        };
+        assert_eq!(None, board.step(Point(0, 3), Direction::Left));
+        assert_eq!(None, board.step(Point(7, 3), Direction::Right));
+        assert_eq!(None, board.step(Point(4, 0), Direction::Up));
+        assert_eq!(None, board.step(Point(4, 5), Direction::Down));
+        assert_eq!(Some(Point(5, 3)), board.step(Point(4, 3), Direction::Right));
+        assert_eq!(7, board.distance(Point(0, 0), Point(7, 0)));
+
+        let board = BoardConfig {
+            topology: Topology::Wrapping,
+            ..board
+        };
+        assert_eq!(Some(Point(7, 3)), board.step(Point(0, 3), Direction::Left));
+        assert_eq!(1, board.distance(Point(0, 0), Point(7, 0)));
+    }
+
+    #[test]
+    fn test_should_reject_board_when_size_is_out_of_range() {
+        let board = BoardConfig::default();
+        assert_eq!(Ok(()), board.validate());
+        assert_eq!(
+            Err(BoardConfigError::TooSmall),
+            BoardConfig { width: 4, ..board }.validate()
+        );
+        assert_eq!(
+            Err(BoardConfigError::TooLarge),
+            BoardConfig {
+                height: 1000,
+                ..board
+            }
+            .validate()
+        );
+    }
+}
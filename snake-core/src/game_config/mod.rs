@@ -0,0 +1,53 @@
+pub mod board;
+pub mod speed_policy;
+
+use crate::creature::CreatureSpawning;
+use crate::game_mode::GameMode;
+use crate::level_map::LevelMap;
+use crate::visibility::VisibilityConfig;
+
+use self::board::BoardConfig;
+use self::speed_policy::SpeedPolicy;
+
+const DEFAULT_LIVES: u32 = 1;
+const DEFAULT_RESPAWN_INVULNERABILITY_TICKS: u32 = 3;
+const DEFAULT_APPLE_SPAWN_ONE_IN: u32 = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShrinkingArenaConfig {
+    pub shrink_interval_ticks: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameConfig {
+    pub mode: GameMode,
+    pub board: BoardConfig,
+    // An apple spawns on average once every this many ticks; 0 disables spawning.
+    pub apple_spawn_one_in: u32,
+    pub speed_policy: SpeedPolicy,
+    pub lives: u32,
+    pub respawn_invulnerability_ticks: u32,
+    pub shrinking_arena: Option<ShrinkingArenaConfig>,
+    pub visibility: Option<VisibilityConfig>,
+    pub level: Option<LevelMap>,
+    pub creature_spawning: Option<CreatureSpawning>,
+    pub seed: Option<u64>,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            mode: GameMode::default(),
+            board: BoardConfig::default(),
+            apple_spawn_one_in: DEFAULT_APPLE_SPAWN_ONE_IN,
+            speed_policy: SpeedPolicy::default(),
+            lives: DEFAULT_LIVES,
+            respawn_invulnerability_ticks: DEFAULT_RESPAWN_INVULNERABILITY_TICKS,
+            shrinking_arena: None,
+            visibility: None,
+            level: None,
+            creature_spawning: None,
+            seed: None,
+        }
+    }
+}
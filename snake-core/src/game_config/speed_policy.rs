@@ -0,0 +1,111 @@
+use alloc::vec::Vec;
+
+use crate::SNAKE_INITIAL_LENGTH;
+
+pub const DEFAULT_TICK_INTERVAL_MILLISECONDS: u64 = 10000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpeedStep {
+    pub score_threshold: u32,
+    pub interval_milliseconds: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SpeedPolicy {
+    Constant {
+        interval_milliseconds: u64,
+    },
+    LengthBased {
+        initial_interval_milliseconds: u64,
+        decrease_per_segment_milliseconds: u64,
+        minimum_interval_milliseconds: u64,
+    },
+    ScoreSteps {
+        initial_interval_milliseconds: u64,
+        steps: Vec<SpeedStep>,
+    },
+}
+
+impl Default for SpeedPolicy {
+    fn default() -> Self {
+        SpeedPolicy::Constant {
+            interval_milliseconds: DEFAULT_TICK_INTERVAL_MILLISECONDS,
+        }
+    }
+}
+
+impl SpeedPolicy {
+    pub fn interval_milliseconds(&self, snake_length: usize, score: u32) -> u64 {
+        match self {
+            SpeedPolicy::Constant {
+                interval_milliseconds,
+            } => *interval_milliseconds,
+            SpeedPolicy::LengthBased {
+                initial_interval_milliseconds,
+                decrease_per_segment_milliseconds,
+                minimum_interval_milliseconds,
+            } => {
+                let grown_segments = snake_length.saturating_sub(SNAKE_INITIAL_LENGTH as usize);
+                let decrease = decrease_per_segment_milliseconds.saturating_mul(grown_segments as u64);
+                initial_interval_milliseconds
+                    .saturating_sub(decrease)
+                    .max(*minimum_interval_milliseconds)
+            }
+            SpeedPolicy::ScoreSteps {
+                initial_interval_milliseconds,
+                steps,
+            } => steps
+                .iter()
+                .filter(|step| step.score_threshold <= score)
+                .max_by_key(|step| step.score_threshold)
+                .map(|step| step.interval_milliseconds)
+                .unwrap_or(*initial_interval_milliseconds),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SpeedPolicy, SpeedStep};
+
+    #[test]
+    fn test_should_return_same_interval_regardless_of_length_and_score_when_constant_policy() {
+        let policy = SpeedPolicy::Constant {
+            interval_milliseconds: 500,
+        };
+        assert_eq!(500, policy.interval_milliseconds(3, 0));
+        assert_eq!(500, policy.interval_milliseconds(40, 25));
+    }
+
+    #[test]
+    fn test_should_shrink_interval_per_grown_segment_down_to_minimum_when_length_based_policy() {
+        let policy = SpeedPolicy::LengthBased {
+            initial_interval_milliseconds: 1000,
+            decrease_per_segment_milliseconds: 100,
+            minimum_interval_milliseconds: 400,
+        };
+        assert_eq!(1000, policy.interval_milliseconds(3, 0));
+        assert_eq!(800, policy.interval_milliseconds(5, 0));
+        assert_eq!(400, policy.interval_milliseconds(30, 0));
+    }
+
+    #[test]
+    fn test_should_use_highest_reached_threshold_when_score_steps_policy() {
+        let policy = SpeedPolicy::ScoreSteps {
+            initial_interval_milliseconds: 1000,
+            steps: vec![
+                SpeedStep {
+                    score_threshold: 10,
+                    interval_milliseconds: 500,
+                },
+                SpeedStep {
+                    score_threshold: 5,
+                    interval_milliseconds: 800,
+                },
+            ],
+        };
+        assert_eq!(1000, policy.interval_milliseconds(3, 4));
+        assert_eq!(800, policy.interval_milliseconds(3, 5));
+        assert_eq!(500, policy.interval_milliseconds(3, 12));
+    }
+}
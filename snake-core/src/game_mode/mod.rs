@@ -0,0 +1,128 @@
+use crate::GameStats;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GameMode {
+    #[default]
+    Endless,
+    TimeLimited { tick_budget: u64 },
+    TargetLength { target_length: usize },
+    Survival { hazard_interval_ticks: u64 },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeathCause {
+    SelfCollision,
+    WallCollision,
+    CaughtByArena,
+    CreatureContact,
+    SnakeCollision,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameOverReason {
+    Died(DeathCause),
+    TimeUp,
+    TargetReached,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GameResult {
+    pub mode: GameMode,
+    pub reason: GameOverReason,
+    pub score: u32,
+    pub final_length: usize,
+    pub stats: GameStats,
+}
+
+impl GameMode {
+    pub fn end_condition(&self, stats: &GameStats, snake_length: usize) -> Option<GameOverReason> {
+        match self {
+            GameMode::TimeLimited { tick_budget } if stats.ticks >= *tick_budget => {
+                Some(GameOverReason::TimeUp)
+            }
+            GameMode::TargetLength { target_length } if snake_length >= *target_length => {
+                Some(GameOverReason::TargetReached)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn score(&self, stats: &GameStats) -> u32 {
+        match self {
+            GameMode::Endless => stats.apples_eaten,
+            GameMode::TimeLimited { .. } | GameMode::TargetLength { .. } => {
+                stats.longest_length as u32
+            }
+            GameMode::Survival { .. } => stats.ticks as u32,
+        }
+    }
+
+    pub fn is_hazard_due(&self, stats: &GameStats) -> bool {
+        match self {
+            GameMode::Survival {
+                hazard_interval_ticks,
+            } => *hazard_interval_ticks > 0 && stats.ticks.is_multiple_of(*hazard_interval_ticks),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GameStats;
+
+    use super::{GameMode, GameOverReason};
+
+    #[test]
+    fn test_should_end_time_limited_game_when_tick_budget_runs_out() {
+        let mode = GameMode::TimeLimited { tick_budget: 10 };
+        let mut stats = GameStats {
+            ticks: 9,
+            ..GameStats::default()
+        };
+        assert_eq!(None, mode.end_condition(&stats, 3));
+        stats.ticks = 10;
+        assert_eq!(Some(GameOverReason::TimeUp), mode.end_condition(&stats, 3));
+    }
+
+    #[test]
+    fn test_should_end_target_length_game_when_target_reached() {
+        let mode = GameMode::TargetLength { target_length: 5 };
+        let stats = GameStats::default();
+        assert_eq!(None, mode.end_condition(&stats, 4));
+        assert_eq!(
+            Some(GameOverReason::TargetReached),
+            mode.end_condition(&stats, 5)
+        );
+    }
+
+    #[test]
+    fn test_should_never_end_endless_and_survival_games_by_rule() {
+        let stats = GameStats {
+            ticks: 1000,
+            ..GameStats::default()
+        };
+        assert_eq!(None, GameMode::Endless.end_condition(&stats, 100));
+        let survival = GameMode::Survival {
+            hazard_interval_ticks: 10,
+        };
+        assert_eq!(None, survival.end_condition(&stats, 100));
+    }
+
+    #[test]
+    fn test_should_score_each_mode_by_its_own_rule() {
+        let stats = GameStats {
+            ticks: 40,
+            apples_eaten: 2,
+            deaths: 0,
+            longest_length: 6,
+        };
+        assert_eq!(2, GameMode::Endless.score(&stats));
+        assert_eq!(6, GameMode::TimeLimited { tick_budget: 50 }.score(&stats));
+        assert_eq!(6, GameMode::TargetLength { target_length: 8 }.score(&stats));
+        let survival = GameMode::Survival {
+            hazard_interval_ticks: 10,
+        };
+        assert_eq!(40, survival.score(&stats));
+    }
+}
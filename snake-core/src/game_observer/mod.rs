@@ -0,0 +1,20 @@
+use core::fmt::Debug;
+
+use crate::game_mode::GameResult;
+use crate::{Direction, Point};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameEvent {
+    GameStarted,
+    SnakeMoved { head: Point, length: usize },
+    AppleSpawned(Point),
+    AppleEaten(Point),
+    CreatureSpawned(Point),
+    DirectionChanged { from: Direction, to: Direction },
+    DirectionRejected { current: Direction, requested: Direction },
+    GameOver(GameResult),
+}
+
+pub trait GameObserver: Debug + Send + Sync {
+    fn on_event(&mut self, event: &GameEvent);
+}
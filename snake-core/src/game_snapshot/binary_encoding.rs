@@ -0,0 +1,440 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::game_config::board::MAX_BOARD_SIDE;
+use crate::{Direction, Point};
+
+use super::GameSnapshot;
+
+const MAGIC: [u8; 2] = *b"SN";
+const FORMAT_VERSION: u8 = 3;
+// Version 1 snapshots predate creatures and version 2 ones predate remembered
+// apples and walls; both are still accepted.
+const FIRST_FORMAT_VERSION: u8 = 1;
+const CREATURES_FORMAT_VERSION: u8 = 2;
+const HEADER_LENGTH: usize = MAGIC.len() + 1;
+const CHECKSUM_LENGTH: usize = 4;
+const MAX_RUN_LENGTH: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapshotEncodeError {
+    DisconnectedBody,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapshotDecodeError {
+    UnexpectedEnd,
+    InvalidMagic,
+    UnsupportedVersion(u8),
+    ChecksumMismatch,
+    Malformed,
+}
+
+impl GameSnapshot {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SnapshotEncodeError> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(FORMAT_VERSION);
+
+        write_varint(&mut bytes, self.tick);
+        write_varint(&mut bytes, self.width as u64);
+        write_varint(&mut bytes, self.height as u64);
+        bytes.push(direction_code(self.direction));
+        write_varint(&mut bytes, self.score as u64);
+        write_varint(&mut bytes, self.lives_left as u64);
+        self.write_body(&mut bytes)?;
+        write_points(&mut bytes, &self.apples);
+        write_points(&mut bytes, &self.walls);
+        write_points(&mut bytes, &self.creatures);
+        write_points(&mut bytes, &self.remembered_apples);
+        write_points(&mut bytes, &self.remembered_walls);
+
+        let checksum = crc32(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SnapshotDecodeError> {
+        if bytes.len() < HEADER_LENGTH + CHECKSUM_LENGTH {
+            return Err(SnapshotDecodeError::UnexpectedEnd);
+        }
+        if bytes[..MAGIC.len()] != MAGIC {
+            return Err(SnapshotDecodeError::InvalidMagic);
+        }
+        let version = bytes[MAGIC.len()];
+        if !(FIRST_FORMAT_VERSION..=FORMAT_VERSION).contains(&version) {
+            return Err(SnapshotDecodeError::UnsupportedVersion(version));
+        }
+        let (content, checksum) = bytes.split_at(bytes.len() - CHECKSUM_LENGTH);
+        if crc32(content).to_le_bytes() != checksum {
+            return Err(SnapshotDecodeError::ChecksumMismatch);
+        }
+
+        let mut reader = Reader {
+            bytes: &content[HEADER_LENGTH..],
+        };
+        let tick = reader.varint()?;
+        let width = reader.varint_u32()?;
+        let height = reader.varint_u32()?;
+        if !(1..=MAX_BOARD_SIDE).contains(&width) || !(1..=MAX_BOARD_SIDE).contains(&height) {
+            return Err(SnapshotDecodeError::Malformed);
+        }
+        let direction = direction_from_code(reader.byte()?)?;
+        let score = reader.varint_u32()?;
+        let lives_left = reader.varint_u32()?;
+        let snake = reader.body(width, height)?;
+        let apples = reader.points(width, height)?;
+        let walls = reader.points(width, height)?;
+        let creatures = match version {
+            FIRST_FORMAT_VERSION => Vec::new(),
+            _ => reader.points(width, height)?,
+        };
+        let (remembered_apples, remembered_walls) = match version {
+            FIRST_FORMAT_VERSION | CREATURES_FORMAT_VERSION => (Vec::new(), Vec::new()),
+            _ => (reader.points(width, height)?, reader.points(width, height)?),
+        };
+        if !reader.bytes.is_empty() {
+            return Err(SnapshotDecodeError::Malformed);
+        }
+
+        Ok(Self {
+            tick,
+            width,
+            height,
+            snake,
+            apples,
+            walls,
+            creatures,
+            remembered_apples,
+            remembered_walls,
+            direction,
+            score,
+            lives_left,
+        })
+    }
+
+    fn write_body(&self, bytes: &mut Vec<u8>) -> Result<(), SnapshotEncodeError> {
+        write_varint(bytes, self.snake.len() as u64);
+        let start = match self.snake.first() {
+            Some(&start) => start,
+            None => return Ok(()),
+        };
+        write_point(bytes, start);
+
+        let mut runs: Vec<(Direction, usize)> = Vec::new();
+        for segments in self.snake.windows(2) {
+            let direction = step_direction(segments[0], segments[1], self.width, self.height)
+                .ok_or(SnapshotEncodeError::DisconnectedBody)?;
+            match runs.last_mut() {
+                Some((run_direction, length))
+                    if *run_direction == direction && *length < MAX_RUN_LENGTH =>
+                {
+                    *length += 1
+                }
+                _ => runs.push((direction, 1)),
+            }
+        }
+
+        write_varint(bytes, runs.len() as u64);
+        for (direction, length) in runs {
+            bytes.push(direction_code(direction) << 6 | (length - 1) as u8);
+        }
+        Ok(())
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn byte(&mut self) -> Result<u8, SnapshotDecodeError> {
+        let (&byte, rest) = self
+            .bytes
+            .split_first()
+            .ok_or(SnapshotDecodeError::UnexpectedEnd)?;
+        self.bytes = rest;
+        Ok(byte)
+    }
+
+    fn varint(&mut self) -> Result<u64, SnapshotDecodeError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(SnapshotDecodeError::Malformed)
+    }
+
+    fn varint_u32(&mut self) -> Result<u32, SnapshotDecodeError> {
+        u32::try_from(self.varint()?).map_err(|_| SnapshotDecodeError::Malformed)
+    }
+
+    fn point(&mut self, width: u32, height: u32) -> Result<Point, SnapshotDecodeError> {
+        let point = Point(self.varint_u32()?, self.varint_u32()?);
+        if point.0 >= width || point.1 >= height {
+            return Err(SnapshotDecodeError::Malformed);
+        }
+        Ok(point)
+    }
+
+    fn points(&mut self, width: u32, height: u32) -> Result<Vec<Point>, SnapshotDecodeError> {
+        let count = self.varint()?;
+        (0..count).map(|_| self.point(width, height)).collect()
+    }
+
+    fn body(&mut self, width: u32, height: u32) -> Result<Vec<Point>, SnapshotDecodeError> {
+        let length = self.varint()? as usize;
+        if length == 0 {
+            return Ok(Vec::new());
+        }
+        let mut segment = self.point(width, height)?;
+        let mut body = vec![segment];
+
+        let runs_count = self.varint()?;
+        for _ in 0..runs_count {
+            let run = self.byte()?;
+            let direction = direction_from_code(run >> 6)?;
+            for _ in 0..=(run & 0x3f) {
+                segment = neighbour(segment, direction, width, height);
+                body.push(segment);
+            }
+        }
+        if body.len() != length {
+            return Err(SnapshotDecodeError::Malformed);
+        }
+        Ok(body)
+    }
+}
+
+fn direction_code(direction: Direction) -> u8 {
+    match direction {
+        Direction::Up => 0,
+        Direction::Down => 1,
+        Direction::Left => 2,
+        Direction::Right => 3,
+    }
+}
+
+fn direction_from_code(code: u8) -> Result<Direction, SnapshotDecodeError> {
+    match code {
+        0 => Ok(Direction::Up),
+        1 => Ok(Direction::Down),
+        2 => Ok(Direction::Left),
+        3 => Ok(Direction::Right),
+        _ => Err(SnapshotDecodeError::Malformed),
+    }
+}
+
+// Wraps without arithmetic on the edges, so any u32 dimensions are safe.
+fn neighbour(point: Point, direction: Direction, width: u32, height: u32) -> Point {
+    let Point(x, y) = point;
+    match direction {
+        Direction::Up if y == 0 => Point(x, height.saturating_sub(1)),
+        Direction::Up => Point(x, y - 1),
+        Direction::Down if y.saturating_add(1) >= height => Point(x, 0),
+        Direction::Down => Point(x, y + 1),
+        Direction::Left if x == 0 => Point(width.saturating_sub(1), y),
+        Direction::Left => Point(x - 1, y),
+        Direction::Right if x.saturating_add(1) >= width => Point(0, y),
+        Direction::Right => Point(x + 1, y),
+    }
+}
+
+fn step_direction(from: Point, to: Point, width: u32, height: u32) -> Option<Direction> {
+    [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ]
+    .into_iter()
+    .find(|&direction| neighbour(from, direction, width, height) == to)
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+fn write_point(bytes: &mut Vec<u8>, point: Point) {
+    write_varint(bytes, point.0 as u64);
+    write_varint(bytes, point.1 as u64);
+}
+
+fn write_points(bytes: &mut Vec<u8>, points: &[Point]) {
+    write_varint(bytes, points.len() as u64);
+    for &point in points {
+        write_point(bytes, point);
+    }
+}
+
+// CRC-32 (IEEE 802.3), computed bitwise to keep the core free of lookup tables.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_snapshot::GameSnapshot;
+    use crate::{Direction, Point};
+
+    use super::{crc32, SnapshotDecodeError, SnapshotEncodeError};
+
+    fn snapshot() -> GameSnapshot {
+        GameSnapshot {
+            tick: 1234,
+            width: 20,
+            height: 10,
+            snake: vec![
+                Point(0, 5),
+                Point(19, 5),
+                Point(18, 5),
+                Point(18, 6),
+                Point(18, 7),
+            ],
+            apples: vec![Point(3, 3), Point(7, 1)],
+            walls: vec![Point(0, 0)],
+            creatures: vec![Point(9, 9)],
+            remembered_apples: vec![Point(12, 2)],
+            remembered_walls: vec![Point(1, 0), Point(2, 0)],
+            direction: Direction::Right,
+            score: 300,
+            lives_left: 2,
+        }
+    }
+
+    #[test]
+    fn test_should_compute_standard_crc32_check_value() {
+        assert_eq!(0xcbf4_3926, crc32(b"123456789"));
+    }
+
+    #[test]
+    fn test_should_decode_same_snapshot_when_encoded() {
+        let bytes = snapshot().to_bytes().expect("snapshot should encode");
+        assert_eq!(Ok(snapshot()), GameSnapshot::from_bytes(&bytes));
+    }
+
+    #[test]
+    fn test_should_encode_long_straight_body_in_few_bytes() {
+        let mut straight = snapshot();
+        straight.width = 200;
+        straight.snake = (0..150).map(|x| Point(x, 5)).collect();
+        straight.remembered_apples.clear();
+        straight.remembered_walls.clear();
+        let bytes = straight.to_bytes().expect("snapshot should encode");
+        assert!(bytes.len() < 40);
+        assert_eq!(Ok(straight), GameSnapshot::from_bytes(&bytes));
+    }
+
+    #[test]
+    fn test_should_reject_corrupted_input_with_checksum_error() {
+        let mut bytes = snapshot().to_bytes().expect("snapshot should encode");
+        bytes[6] ^= 0x01;
+        assert_eq!(
+            Err(SnapshotDecodeError::ChecksumMismatch),
+            GameSnapshot::from_bytes(&bytes)
+        );
+    }
+
+    #[test]
+    fn test_should_reject_unknown_version() {
+        let mut bytes = snapshot().to_bytes().expect("snapshot should encode");
+        bytes[2] = 99;
+        assert_eq!(
+            Err(SnapshotDecodeError::UnsupportedVersion(99)),
+            GameSnapshot::from_bytes(&bytes)
+        );
+    }
+
+    #[test]
+    fn test_should_decode_version_1_snapshot_without_creatures() {
+        let mut expected = snapshot();
+        expected.creatures.clear();
+        expected.remembered_apples.clear();
+        expected.remembered_walls.clear();
+        let bytes = expected.to_bytes().expect("snapshot should encode");
+        // Version 1 is the same layout without the trailing creature and
+        // remembered point counts.
+        let mut legacy = bytes[..bytes.len() - 7].to_vec();
+        legacy[2] = 1;
+        let checksum = crc32(&legacy);
+        legacy.extend_from_slice(&checksum.to_le_bytes());
+        assert_eq!(Ok(expected), GameSnapshot::from_bytes(&legacy));
+    }
+
+    #[test]
+    fn test_should_decode_version_2_snapshot_without_remembered_points() {
+        let mut expected = snapshot();
+        expected.remembered_apples.clear();
+        expected.remembered_walls.clear();
+        let bytes = expected.to_bytes().expect("snapshot should encode");
+        let mut legacy = bytes[..bytes.len() - 6].to_vec();
+        legacy[2] = 2;
+        let checksum = crc32(&legacy);
+        legacy.extend_from_slice(&checksum.to_le_bytes());
+        assert_eq!(Ok(expected), GameSnapshot::from_bytes(&legacy));
+    }
+
+    #[test]
+    fn test_should_reject_truncated_and_foreign_input() {
+        assert_eq!(
+            Err(SnapshotDecodeError::UnexpectedEnd),
+            GameSnapshot::from_bytes(b"SN")
+        );
+        assert_eq!(
+            Err(SnapshotDecodeError::InvalidMagic),
+            GameSnapshot::from_bytes(b"GIF89a;\0\0")
+        );
+    }
+
+    #[test]
+    fn test_should_reject_board_dimensions_outside_supported_range() {
+        let mut huge = snapshot();
+        huge.width = u32::MAX;
+        huge.height = u32::MAX;
+        huge.snake = vec![Point(0, 5), Point(0, 4), Point(0, 3)];
+        let bytes = huge.to_bytes().expect("snapshot should encode");
+        assert_eq!(
+            Err(SnapshotDecodeError::Malformed),
+            GameSnapshot::from_bytes(&bytes)
+        );
+
+        let mut empty = snapshot();
+        empty.width = 0;
+        empty.snake.clear();
+        empty.apples.clear();
+        empty.walls.clear();
+        empty.creatures.clear();
+        empty.remembered_apples.clear();
+        empty.remembered_walls.clear();
+        let bytes = empty.to_bytes().expect("snapshot should encode");
+        assert_eq!(
+            Err(SnapshotDecodeError::Malformed),
+            GameSnapshot::from_bytes(&bytes)
+        );
+    }
+
+    #[test]
+    fn test_should_fail_to_encode_when_body_segments_are_not_adjacent() {
+        let mut disconnected = snapshot();
+        disconnected.snake = vec![Point(0, 0), Point(5, 5)];
+        assert_eq!(
+            Err(SnapshotEncodeError::DisconnectedBody),
+            disconnected.to_bytes()
+        );
+    }
+}
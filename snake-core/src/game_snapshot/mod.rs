@@ -0,0 +1,87 @@
+pub mod binary_encoding;
+pub mod state_delta;
+
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::{Direction, Point};
+
+use self::state_delta::{DeltaApplyError, StateDelta};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameSnapshot {
+    pub tick: u64,
+    pub width: u32,
+    pub height: u32,
+    pub snake: Vec<Point>,
+    pub apples: Vec<Point>,
+    pub walls: Vec<Point>,
+    pub creatures: Vec<Point>,
+    // Apples and walls seen earlier under fog of war that may be gone by now.
+    pub remembered_apples: Vec<Point>,
+    pub remembered_walls: Vec<Point>,
+    pub direction: Direction,
+    pub score: u32,
+    pub lives_left: u32,
+}
+
+impl GameSnapshot {
+    pub fn head(&self) -> Option<Point> {
+        self.snake.first().cloned()
+    }
+
+    pub fn apply_delta(&mut self, delta: &StateDelta) -> Result<(), DeltaApplyError> {
+        if delta.from_tick != self.tick {
+            return Err(DeltaApplyError::Gap {
+                expected_tick: self.tick,
+                received_tick: delta.from_tick,
+            });
+        }
+        let kept_length = self
+            .snake
+            .len()
+            .checked_sub(delta.tail_removed)
+            .ok_or(DeltaApplyError::Inconsistent)?;
+
+        let mut snake = delta.head_added.clone();
+        snake.extend_from_slice(&self.snake[..kept_length]);
+        snake.extend_from_slice(&delta.tail_added);
+        self.snake = snake;
+
+        self.apples = apply_set_changes(&self.apples, &delta.apples_added, &delta.apples_removed);
+        self.walls = apply_set_changes(&self.walls, &delta.walls_added, &delta.walls_removed);
+        self.remembered_apples = apply_set_changes(
+            &self.remembered_apples,
+            &delta.remembered_apples_added,
+            &delta.remembered_apples_removed,
+        );
+        self.remembered_walls = apply_set_changes(
+            &self.remembered_walls,
+            &delta.remembered_walls_added,
+            &delta.remembered_walls_removed,
+        );
+        if let Some(creatures) = &delta.creatures {
+            self.creatures = creatures.clone();
+        }
+        if let Some(direction) = delta.direction {
+            self.direction = direction;
+        }
+        if let Some(score) = delta.score {
+            self.score = score;
+        }
+        if let Some(lives_left) = delta.lives_left {
+            self.lives_left = lives_left;
+        }
+        self.tick = delta.to_tick;
+        Ok(())
+    }
+}
+
+fn apply_set_changes(points: &[Point], added: &[Point], removed: &[Point]) -> Vec<Point> {
+    let mut points = points.iter().cloned().collect::<BTreeSet<Point>>();
+    for point in removed {
+        points.remove(point);
+    }
+    points.extend(added.iter().cloned());
+    points.into_iter().collect()
+}
@@ -0,0 +1,201 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::{Direction, Point};
+
+use super::GameSnapshot;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateDelta {
+    pub from_tick: u64,
+    pub to_tick: u64,
+    pub head_added: Vec<Point>,
+    pub tail_removed: usize,
+    pub tail_added: Vec<Point>,
+    pub apples_added: Vec<Point>,
+    pub apples_removed: Vec<Point>,
+    pub walls_added: Vec<Point>,
+    pub walls_removed: Vec<Point>,
+    pub remembered_apples_added: Vec<Point>,
+    pub remembered_apples_removed: Vec<Point>,
+    pub remembered_walls_added: Vec<Point>,
+    pub remembered_walls_removed: Vec<Point>,
+    pub creatures: Option<Vec<Point>>,
+    pub direction: Option<Direction>,
+    pub score: Option<u32>,
+    pub lives_left: Option<u32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeltaApplyError {
+    Gap {
+        expected_tick: u64,
+        received_tick: u64,
+    },
+    Inconsistent,
+}
+
+impl StateDelta {
+    pub fn between(previous: &GameSnapshot, current: &GameSnapshot) -> Self {
+        let (head_added, kept_length) = snake_overlap(&previous.snake, &current.snake);
+        let (apples_added, apples_removed) = set_changes(&previous.apples, &current.apples);
+        let (walls_added, walls_removed) = set_changes(&previous.walls, &current.walls);
+        let (remembered_apples_added, remembered_apples_removed) =
+            set_changes(&previous.remembered_apples, &current.remembered_apples);
+        let (remembered_walls_added, remembered_walls_removed) =
+            set_changes(&previous.remembered_walls, &current.remembered_walls);
+
+        Self {
+            from_tick: previous.tick,
+            to_tick: current.tick,
+            head_added: current.snake[..head_added].to_vec(),
+            tail_removed: previous.snake.len() - kept_length,
+            tail_added: current.snake[head_added + kept_length..].to_vec(),
+            apples_added,
+            apples_removed,
+            walls_added,
+            walls_removed,
+            remembered_apples_added,
+            remembered_apples_removed,
+            remembered_walls_added,
+            remembered_walls_removed,
+            creatures: (previous.creatures != current.creatures).then(|| current.creatures.clone()),
+            direction: changed(previous.direction, current.direction),
+            score: changed(previous.score, current.score),
+            lives_left: changed(previous.lives_left, current.lives_left),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head_added.is_empty()
+            && self.tail_removed == 0
+            && self.tail_added.is_empty()
+            && self.apples_added.is_empty()
+            && self.apples_removed.is_empty()
+            && self.walls_added.is_empty()
+            && self.walls_removed.is_empty()
+            && self.remembered_apples_added.is_empty()
+            && self.remembered_apples_removed.is_empty()
+            && self.remembered_walls_added.is_empty()
+            && self.remembered_walls_removed.is_empty()
+            && self.creatures.is_none()
+            && self.direction.is_none()
+            && self.score.is_none()
+            && self.lives_left.is_none()
+    }
+}
+
+// Finds how many segments were pushed in front of the previous body and how
+// many previous segments are still in place right after them.
+fn snake_overlap(previous: &[Point], current: &[Point]) -> (usize, usize) {
+    (0..current.len())
+        .map(|offset| {
+            let kept_length = current[offset..]
+                .iter()
+                .zip(previous.iter())
+                .take_while(|(current, previous)| current == previous)
+                .count();
+            (offset, kept_length)
+        })
+        .find(|&(_, kept_length)| kept_length > 0)
+        .unwrap_or((current.len(), 0))
+}
+
+fn set_changes(previous: &[Point], current: &[Point]) -> (Vec<Point>, Vec<Point>) {
+    let previous = previous.iter().cloned().collect::<BTreeSet<Point>>();
+    let current = current.iter().cloned().collect::<BTreeSet<Point>>();
+    let added = current.difference(&previous).cloned().collect();
+    let removed = previous.difference(&current).cloned().collect();
+    (added, removed)
+}
+
+fn changed<T: PartialEq>(previous: T, current: T) -> Option<T> {
+    if previous == current {
+        None
+    } else {
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_snapshot::GameSnapshot;
+    use crate::{Direction, Point};
+
+    use super::{DeltaApplyError, StateDelta};
+
+    fn snapshot(tick: u64, snake: Vec<Point>, apples: Vec<Point>) -> GameSnapshot {
+        GameSnapshot {
+            tick,
+            width: 20,
+            height: 10,
+            snake,
+            apples,
+            walls: vec![],
+            creatures: vec![],
+            remembered_apples: vec![],
+            remembered_walls: vec![],
+            direction: Direction::Left,
+            score: 0,
+            lives_left: 1,
+        }
+    }
+
+    #[test]
+    fn test_should_describe_move_as_head_added_and_tail_removed() {
+        let previous = snapshot(1, vec![Point(10, 5), Point(11, 5), Point(12, 5)], vec![]);
+        let current = snapshot(2, vec![Point(9, 5), Point(10, 5), Point(11, 5)], vec![]);
+        let delta = StateDelta::between(&previous, &current);
+        assert_eq!(vec![Point(9, 5)], delta.head_added);
+        assert_eq!(1, delta.tail_removed);
+        assert!(delta.tail_added.is_empty());
+        assert_eq!(None, delta.direction);
+    }
+
+    #[test]
+    fn test_should_rebuild_current_snapshot_when_delta_applied_to_previous() {
+        let previous = snapshot(
+            4,
+            vec![Point(10, 5), Point(10, 6), Point(11, 6)],
+            vec![Point(9, 5), Point(0, 0)],
+        );
+        let mut current = snapshot(
+            5,
+            vec![Point(9, 5), Point(10, 5), Point(10, 6), Point(10, 7)],
+            vec![Point(0, 0), Point(3, 3)],
+        );
+        current.direction = Direction::Up;
+        current.score = 1;
+        let delta = StateDelta::between(&previous, &current);
+
+        let mut synced = previous.clone();
+        synced
+            .apply_delta(&delta)
+            .expect("delta should apply to its base snapshot");
+        assert_eq!(current, synced);
+    }
+
+    #[test]
+    fn test_should_replace_whole_snake_when_bodies_do_not_overlap() {
+        let previous = snapshot(1, vec![Point(1, 1), Point(2, 1), Point(3, 1)], vec![]);
+        let current = snapshot(2, vec![Point(10, 5), Point(11, 5), Point(12, 5)], vec![]);
+        let delta = StateDelta::between(&previous, &current);
+        assert_eq!(current.snake, delta.head_added);
+        assert_eq!(3, delta.tail_removed);
+    }
+
+    #[test]
+    fn test_should_reject_delta_when_ticks_are_missing() {
+        let previous = snapshot(1, vec![Point(10, 5), Point(11, 5), Point(12, 5)], vec![]);
+        let current = snapshot(2, vec![Point(9, 5), Point(10, 5), Point(11, 5)], vec![]);
+        let delta = StateDelta::between(&previous, &current);
+        let mut stale = snapshot(0, vec![], vec![]);
+        assert_eq!(
+            Err(DeltaApplyError::Gap {
+                expected_tick: 0,
+                received_tick: 1
+            }),
+            stale.apply_delta(&delta)
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use alloc::vec::Vec;
+
+use crate::creature::CreatureBehavior;
+use crate::game_config::board::{BoardConfig, MAX_BOARD_SIDE};
+use crate::{Direction, Point};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LevelMap {
+    pub walls: Vec<Point>,
+    pub creatures: Vec<(Point, CreatureBehavior)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LevelMapError {
+    TooLarge,
+    UnknownGlyph(char),
+}
+
+impl LevelMap {
+    // One character per cell: `#` wall, `<` `>` `^` `v` patrolling creature,
+    // `R` random walker, `C` head chaser, `.` or space for an empty cell.
+    // The board a map is played on is only known later, so here a map is only
+    // held to the largest board; `validate` checks it against the actual one.
+    pub fn parse(text: &str) -> Result<Self, LevelMapError> {
+        let mut level = LevelMap::default();
+        for (y, line) in text.lines().enumerate() {
+            for (x, glyph) in line.chars().enumerate() {
+                if x >= MAX_BOARD_SIDE as usize || y >= MAX_BOARD_SIDE as usize {
+                    return Err(LevelMapError::TooLarge);
+                }
+                let point = Point(x as u32, y as u32);
+                let behavior = match glyph {
+                    '.' | ' ' => continue,
+                    '#' => {
+                        level.walls.push(point);
+                        continue;
+                    }
+                    '<' => patrol(Direction::Left),
+                    '>' => patrol(Direction::Right),
+                    '^' => patrol(Direction::Up),
+                    'v' => patrol(Direction::Down),
+                    'R' => CreatureBehavior::RandomWalk,
+                    'C' => CreatureBehavior::ChaseHead,
+                    _ => return Err(LevelMapError::UnknownGlyph(glyph)),
+                };
+                level.creatures.push((point, behavior));
+            }
+        }
+        Ok(level)
+    }
+
+    pub fn validate(&self, board: &BoardConfig) -> Result<(), LevelMapError> {
+        let fits = self
+            .walls
+            .iter()
+            .chain(self.creatures.iter().map(|(position, _)| position))
+            .all(|&point| board.contains(point));
+        if !fits {
+            return Err(LevelMapError::TooLarge);
+        }
+        Ok(())
+    }
+}
+
+fn patrol(direction: Direction) -> CreatureBehavior {
+    CreatureBehavior::Patrol {
+        direction,
+        range: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::creature::CreatureBehavior;
+    use crate::game_config::board::BoardConfig;
+    use crate::{Direction, Point};
+
+    use super::{LevelMap, LevelMapError};
+
+    #[test]
+    fn test_should_parse_walls_and_creatures_from_level_text() {
+        let level = LevelMap::parse("#..>\n.C\n").expect("level should parse");
+        assert_eq!(vec![Point(0, 0)], level.walls);
+        assert_eq!(
+            vec![
+                (
+                    Point(3, 0),
+                    CreatureBehavior::Patrol {
+                        direction: Direction::Right,
+                        range: None
+                    }
+                ),
+                (Point(1, 1), CreatureBehavior::ChaseHead),
+            ],
+            level.creatures
+        );
+        assert_eq!(Err(LevelMapError::UnknownGlyph('x')), LevelMap::parse("x"));
+        assert_eq!(
+            Err(LevelMapError::TooLarge),
+            LevelMap::parse(&".".repeat(257))
+        );
+    }
+
+    #[test]
+    fn test_should_accept_level_wider_than_default_board_only_when_board_fits_it() {
+        let level = LevelMap::parse(&format!("{}#", ".".repeat(24))).expect("level should parse");
+        assert_eq!(
+            Err(LevelMapError::TooLarge),
+            level.validate(&BoardConfig::default())
+        );
+        let board = BoardConfig {
+            width: 25,
+            ..BoardConfig::default()
+        };
+        assert_eq!(Ok(()), level.validate(&board));
+    }
+}
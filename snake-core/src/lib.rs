@@ -1,27 +1,60 @@
-use rand::distributions::Uniform;
-use rand::prelude::Distribution;
+#![cfg_attr(not(feature = "std"), no_std)]
 
-use std::cmp::PartialEq;
-use std::collections::{HashSet, LinkedList};
-use std::vec::Vec;
+extern crate alloc;
+
+pub mod ansi_renderer;
+pub mod creature;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod game_config;
+pub mod game_mode;
+pub mod game_observer;
+pub mod game_snapshot;
+pub mod level_map;
+pub mod multi_snake;
+pub mod rule_set;
+pub mod visibility;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{RngCore, SeedableRng};
+
+use alloc::boxed::Box;
+use alloc::collections::{BTreeSet, LinkedList};
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::PartialEq;
+
+use crate::creature::Creature;
+use crate::game_config::board::BoardConfig;
+use crate::game_config::GameConfig;
+use crate::game_mode::{DeathCause, GameMode, GameOverReason, GameResult};
+use crate::game_observer::{GameEvent, GameObserver};
+use crate::game_snapshot::{state_delta::StateDelta, GameSnapshot};
+use crate::rule_set::{CollisionOutcome, RuleSet};
+use crate::visibility::{VisibilityConfig, VisibleCell};
 
 const SNAKE_INITIAL_LENGTH: i32 = 3;
-const SNAKE_INITIAL_POSITION_X: u32 = 10;
-const SNAKE_INITIAL_POSITION_Y: u32 = 5;
 
-const PLANE_WIDTH: u32 = 20;
-const PLANE_HEIGHT: u32 = 10;
+const HAZARD_HEAD_CLEARANCE: u32 = 2;
 
-const APPLE_GENERATION_CHANCE_PER_MOVE: f32 = 1. / 20.;
+pub(crate) const APPLE_RNG_STREAM: u64 = 1;
+pub(crate) const HAZARD_RNG_STREAM: u64 = 2;
+const CREATURE_RNG_STREAM: u64 = 3;
+const CREATURE_SPAWN_RNG_STREAM: u64 = 4;
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PlaneField {
     Empty,
     Body,
     Apple,
+    Wall,
+    Creature,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Direction {
     Up,
     Down,
@@ -35,35 +68,132 @@ pub struct DirectionChangeError;
 #[derive(Debug, Clone, PartialEq)]
 struct AppleInsertError;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub struct Point(u32, u32);
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Step {
+    Move(Point),
+    Stay,
+    Die(DeathCause),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point(pub u32, pub u32);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GameStats {
+    pub ticks: u64,
+    pub apples_eaten: u32,
+    pub deaths: u32,
+    pub longest_length: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TickResult {
+    pub game_over: Option<GameResult>,
+    pub delta: StateDelta,
+}
 
 #[derive(Debug)]
 pub struct SnakeContext {
-    plane: [[PlaneField; PLANE_WIDTH as usize]; PLANE_HEIGHT as usize],
+    plane: Vec<Vec<PlaneField>>,
+    remembered: Vec<Vec<Option<PlaneField>>>,
     snake: LinkedList<Point>,
-    apples: HashSet<Point>,
+    apples: BTreeSet<Point>,
+    walls: BTreeSet<Point>,
+    creatures: Vec<Creature>,
+    arena_inset: u32,
     direction: Direction,
+    tick: u64,
+    game_id: u64,
+    tick_snapshot: GameSnapshot,
+    seed: u64,
+    stats: GameStats,
+    lives_left: u32,
+    invulnerable_ticks_left: u32,
+    last_game_result: Option<GameResult>,
+    config: GameConfig,
+    rules: Arc<dyn RuleSet>,
+    observers: Vec<Box<dyn GameObserver>>,
+}
+
+#[cfg(feature = "std")]
+impl Default for SnakeContext {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SnakeContext {
+    #[cfg(feature = "std")]
     pub fn new() -> Self {
-        Self {
-            plane: [[PlaneField::Empty; PLANE_WIDTH as usize]; PLANE_HEIGHT as usize],
+        Self::with_config(GameConfig::default())
+    }
+
+    #[cfg(feature = "std")]
+    pub fn with_config(config: GameConfig) -> Self {
+        Self::with_rule_set(config, Arc::new(rule_set::DefaultRuleSet))
+    }
+
+    #[cfg(feature = "std")]
+    pub fn with_rule_set(config: GameConfig, rules: Arc<dyn RuleSet>) -> Self {
+        Self::with_rng(config, rules, &mut rand::thread_rng())
+    }
+
+    // Without `std` there is no thread-local RNG, so the caller supplies the
+    // entropy used to seed games that have no fixed seed in their config.
+    pub fn with_rng(config: GameConfig, rules: Arc<dyn RuleSet>, rng: &mut dyn RngCore) -> Self {
+        let mut context = Self {
+            plane: board_cells(&config.board, PlaneField::Empty),
+            remembered: board_cells(&config.board, None),
             snake: LinkedList::new(),
-            apples: HashSet::new(),
+            apples: BTreeSet::new(),
+            walls: BTreeSet::new(),
+            creatures: Vec::new(),
+            arena_inset: 0,
             direction: Direction::Left,
-        }
+            tick: 0,
+            game_id: 0,
+            tick_snapshot: GameSnapshot {
+                tick: 0,
+                width: config.board.width,
+                height: config.board.height,
+                snake: Vec::new(),
+                apples: Vec::new(),
+                walls: Vec::new(),
+                creatures: Vec::new(),
+                remembered_apples: Vec::new(),
+                remembered_walls: Vec::new(),
+                direction: Direction::Left,
+                score: 0,
+                lives_left: 0,
+            },
+            seed: config.seed.unwrap_or_else(|| rng.next_u64()),
+            stats: GameStats::default(),
+            lives_left: config.lives,
+            invulnerable_ticks_left: 0,
+            last_game_result: None,
+            config,
+            rules,
+            observers: Vec::new(),
+        };
+        context.tick_snapshot = context.snapshot();
+        context
     }
 
     pub fn get_plane_string(&self) -> String {
-        let mut str_buff = String::with_capacity(PLANE_WIDTH as usize * PLANE_HEIGHT as usize);
-        for i in self.plane {
-            for j in i {
-                let char_to_display = match j {
-                    PlaneField::Empty => '-',
-                    PlaneField::Body => 'X',
-                    PlaneField::Apple => '0',
+        let BoardConfig { width, height, .. } = self.config.board;
+        let mut str_buff = String::with_capacity((width as usize + 1) * height as usize);
+        for y in 0..height {
+            for x in 0..width {
+                let char_to_display = match self.visible_cell(Point(x, y)) {
+                    VisibleCell::Visible(PlaneField::Empty) => '-',
+                    VisibleCell::Visible(PlaneField::Body) => 'X',
+                    VisibleCell::Visible(PlaneField::Apple) => '0',
+                    VisibleCell::Visible(PlaneField::Wall) => '#',
+                    VisibleCell::Visible(PlaneField::Creature) => '@',
+                    VisibleCell::Remembered(PlaneField::Apple) => 'o',
+                    VisibleCell::Remembered(PlaneField::Wall) => '+',
+                    VisibleCell::Remembered(_) => '.',
+                    VisibleCell::Hidden => '?',
                 };
                 str_buff.push(char_to_display);
             }
@@ -73,24 +203,252 @@ impl SnakeContext {
     }
 
     pub fn new_game(&mut self) {
+        // Restarting between ticks is a state transition of its own, so clients
+        // holding older snapshots see a gap and resynchronize.
+        self.start_game();
+        self.tick += 1;
+        self.tick_snapshot = self.snapshot();
+    }
+
+    fn start_game(&mut self) {
+        self.game_id += 1;
         self.reset_game();
-        self.generate_snake();
+        self.generate_snake(self.initial_head_position());
+        self.place_level();
         self.clear_plane();
         self.update_plane();
+        self.notify(GameEvent::GameStarted);
+    }
+
+    pub fn register_observer(&mut self, observer: Box<dyn GameObserver>) {
+        self.observers.push(observer);
+    }
+
+    fn notify(&mut self, event: GameEvent) {
+        for observer in &mut self.observers {
+            observer.on_event(&event);
+        }
     }
 
     fn reset_game(&mut self) {
         self.snake.clear();
         self.apples.clear();
+        self.walls.clear();
+        self.creatures.clear();
+        self.arena_inset = 0;
         self.direction = Direction::Left;
+        self.stats = GameStats::default();
+        self.lives_left = self.config.lives;
+        self.invulnerable_ticks_left = 0;
+        self.remembered = board_cells(&self.config.board, None);
+    }
+
+    fn initial_head_position(&self) -> Point {
+        Point(self.config.board.width / 2, self.config.board.height / 2)
     }
 
-    fn generate_snake(&mut self) {
+    // Level cells under the starting snake are skipped so a game never starts
+    // with an immediate collision.
+    fn place_level(&mut self) {
+        let level = match &self.config.level {
+            Some(level) => level,
+            None => return,
+        };
+        let snake = &self.snake;
+        let board = &self.config.board;
+        self.walls.extend(
+            level
+                .walls
+                .iter()
+                .filter(|&&wall| board.contains(wall) && !snake.contains(&wall))
+                .cloned(),
+        );
+        self.creatures.extend(
+            level
+                .creatures
+                .iter()
+                .filter(|&&(position, _)| board.contains(position) && !snake.contains(&position))
+                .map(|&(position, behavior)| Creature::new(position, behavior)),
+        );
+    }
+
+    fn generate_snake(&mut self, head: Point) {
+        let Point(head_x, head_y) = head;
         let values = (0..SNAKE_INITIAL_LENGTH)
-            .into_iter()
             .map(|value| value as u32)
-            .map(|offset| Point(SNAKE_INITIAL_POSITION_X + offset, SNAKE_INITIAL_POSITION_Y));
+            .map(|offset| Point((head_x + offset) % self.config.board.width, head_y));
         self.snake.extend(values);
+        self.update_longest_length();
+    }
+
+    fn update_longest_length(&mut self) {
+        self.stats.longest_length = self.stats.longest_length.max(self.snake.len());
+    }
+
+    fn lose_life(&mut self, cause: DeathCause) -> Option<GameResult> {
+        self.stats.deaths += 1;
+        self.lives_left = self.lives_left.saturating_sub(1);
+        if self.lives_left == 0 {
+            return Some(self.end_game(GameOverReason::Died(cause)));
+        }
+
+        match self.find_safe_spawn_position() {
+            Some(head) => {
+                self.respawn_snake(head);
+                None
+            }
+            None => Some(self.end_game(GameOverReason::Died(cause))),
+        }
+    }
+
+    fn end_game(&mut self, reason: GameOverReason) -> GameResult {
+        let result = self.game_result(reason);
+        self.last_game_result = Some(result);
+        self.notify(GameEvent::GameOver(result));
+        self.start_game();
+        result
+    }
+
+    fn game_result(&self, reason: GameOverReason) -> GameResult {
+        GameResult {
+            mode: self.config.mode,
+            reason,
+            score: self.score(),
+            final_length: self.snake.len(),
+            stats: self.stats,
+        }
+    }
+
+    fn respawn_snake(&mut self, head: Point) {
+        self.snake.clear();
+        self.generate_snake(head);
+        self.direction = Direction::Left;
+        self.invulnerable_ticks_left = self.config.respawn_invulnerability_ticks;
+        self.update_plane();
+    }
+
+    fn find_safe_spawn_position(&self) -> Option<Point> {
+        self.find_spawn_position_from(self.initial_head_position(), |_| true)
+    }
+
+    // `is_free` lets a caller rule out fields this context does not know about.
+    fn find_spawn_position_from(
+        &self,
+        start: Point,
+        is_free: impl Fn(Point) -> bool,
+    ) -> Option<Point> {
+        let width = self.config.board.width;
+        let fields_count = width * self.config.board.height;
+        let Point(initial_x, initial_y) = start;
+        let initial_index = initial_y * width + initial_x;
+        (0..fields_count)
+            .map(|offset| (initial_index + offset) % fields_count)
+            .map(|index| Point(index % width, index / width))
+            .find(|&head| self.is_safe_spawn_position(head, &is_free))
+    }
+
+    fn is_safe_spawn_position(&self, head: Point, is_free: &impl Fn(Point) -> bool) -> bool {
+        let board = &self.config.board;
+        // The field in front of the head has to be free as well, and on a walled
+        // board the whole snake has to fit between the edges.
+        let front = board.step(head, Direction::Left);
+        let body = (1..SNAKE_INITIAL_LENGTH).scan(Some(head), |point, _| {
+            *point = point.and_then(|point| board.step(point, Direction::Right));
+            Some(*point)
+        });
+        core::iter::once(front)
+            .chain(core::iter::once(Some(head)))
+            .chain(body)
+            .all(|point| match point {
+                Some(point) => {
+                    is_free(point)
+                        && !self.apples.contains(&point)
+                        && !self.walls.contains(&point)
+                        && !self.is_creature_at(point)
+                }
+                None => false,
+            })
+    }
+
+    pub fn ticks_until_next_shrink(&self) -> Option<u64> {
+        let shrink_interval_ticks = self.config.shrinking_arena?.shrink_interval_ticks;
+        if shrink_interval_ticks == 0 || !self.can_shrink_arena() {
+            return None;
+        }
+        Some(shrink_interval_ticks - self.stats.ticks % shrink_interval_ticks)
+    }
+
+    fn can_shrink_arena(&self) -> bool {
+        let next_inset = self.arena_inset + 1;
+        let BoardConfig { width, height, .. } = self.config.board;
+        width > 2 * next_inset + SNAKE_INITIAL_LENGTH as u32 && height > 2 * next_inset
+    }
+
+    fn shrink_arena(&mut self) {
+        let ring = self.arena_inset;
+        let (left, right) = (ring, self.config.board.width - 1 - ring);
+        let (top, bottom) = (ring, self.config.board.height - 1 - ring);
+        let ring_points = (left..=right)
+            .flat_map(|x| [Point(x, top), Point(x, bottom)])
+            .chain((top..=bottom).flat_map(|y| [Point(left, y), Point(right, y)]))
+            .collect::<Vec<Point>>();
+
+        for point in ring_points {
+            self.apples.remove(&point);
+            self.walls.insert(point);
+        }
+        let walls = &self.walls;
+        self.creatures
+            .retain(|creature| !walls.contains(&creature.position));
+        self.arena_inset += 1;
+    }
+
+    fn is_creature_at(&self, point: Point) -> bool {
+        self.creatures
+            .iter()
+            .any(|creature| creature.position == point)
+    }
+
+    fn is_snake_touching_creature(&self) -> bool {
+        self.creatures
+            .iter()
+            .any(|creature| self.snake.contains(&creature.position))
+    }
+
+    fn move_creatures(&mut self) {
+        let head = self.head();
+        let board = self.config.board;
+        let mut rng = self.tick_rng(CREATURE_RNG_STREAM);
+        for index in 0..self.creatures.len() {
+            let mut creature = self.creatures[index];
+            creature.step(head, &board, &mut rng, |point| {
+                !self.walls.contains(&point) && !self.is_creature_at(point)
+            });
+            self.creatures[index] = creature;
+        }
+    }
+
+    fn spawn_creature(&mut self) {
+        let spawning = match self.config.creature_spawning {
+            Some(spawning) if spawning.interval_ticks > 0 => spawning,
+            _ => return,
+        };
+        if !self.stats.ticks.is_multiple_of(spawning.interval_ticks)
+            || self.creatures.len() >= spawning.max_creatures
+        {
+            return;
+        }
+
+        let mut rng = self.tick_rng(CREATURE_SPAWN_RNG_STREAM);
+        if let Some(&position) = self.free_fields_away_from_head().choose(&mut rng) {
+            self.creatures
+                .push(Creature::new(position, spawning.behavior));
+            self.notify(GameEvent::CreatureSpawned(position));
+        }
+    }
+
+    fn is_snake_caught_in_walls(&self) -> bool {
+        self.snake.iter().any(|body| self.walls.contains(body))
     }
 
     pub fn change_direction(&mut self, direction: Direction) -> Result<(), DirectionChangeError> {
@@ -106,14 +464,20 @@ impl SnakeContext {
             .collect::<Vec<u32>>();
         let is_faced_horizontally = elements[0] == elements[1];
         if is_faced_horizontally && (direction == Direction::Left || direction == Direction::Right)
+            || !is_faced_horizontally
+                && (direction == Direction::Up || direction == Direction::Down)
         {
-            return Err(DirectionChangeError);
-        } else if !is_faced_horizontally
-            && (direction == Direction::Up || direction == Direction::Down)
-        {
+            self.notify(GameEvent::DirectionRejected {
+                current: self.direction,
+                requested: direction,
+            });
             return Err(DirectionChangeError);
         }
 
+        self.notify(GameEvent::DirectionChanged {
+            from: self.direction,
+            to: direction,
+        });
         self.direction = direction;
         Ok(())
     }
@@ -122,62 +486,345 @@ impl SnakeContext {
         self.direction
     }
 
-    pub fn update_position(&mut self) {
-        self.snake.pop_back();
-        let Point(snake_head_x, snake_head_y) =
-            *self.snake.front().expect("Snake should not be empty");
-        let new_head = match self.direction {
-            Direction::Up if snake_head_y == 0 => Point(snake_head_x, PLANE_HEIGHT - 1),
-            Direction::Up => Point(snake_head_x, snake_head_y - 1),
-            Direction::Down if snake_head_y >= PLANE_HEIGHT - 1 => Point(snake_head_x, 0),
-            Direction::Down => Point(snake_head_x, snake_head_y + 1),
-            Direction::Left if snake_head_x == 0 => Point(PLANE_WIDTH - 1, snake_head_y),
-            Direction::Left => Point(snake_head_x - 1, snake_head_y),
-            Direction::Right if snake_head_x >= PLANE_WIDTH - 1 => Point(0, snake_head_y),
-            Direction::Right => Point(snake_head_x + 1, snake_head_y),
+    pub fn score(&self) -> u32 {
+        self.config.mode.score(&self.stats)
+    }
+
+    pub fn game_mode(&self) -> GameMode {
+        self.config.mode
+    }
+
+    pub fn last_game_result(&self) -> Option<GameResult> {
+        self.last_game_result
+    }
+
+    pub fn plane_width(&self) -> u32 {
+        self.config.board.width
+    }
+
+    pub fn plane_height(&self) -> u32 {
+        self.config.board.height
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    pub fn head(&self) -> Point {
+        *self.snake.front().expect("Snake should not be empty")
+    }
+
+    pub fn snake_segments(&self) -> impl Iterator<Item = Point> + '_ {
+        self.snake.iter().cloned()
+    }
+
+    pub fn apples(&self) -> impl Iterator<Item = Point> + '_ {
+        self.apples.iter().cloned()
+    }
+
+    pub fn walls(&self) -> impl Iterator<Item = Point> + '_ {
+        self.walls.iter().cloned()
+    }
+
+    pub fn creatures(&self) -> impl Iterator<Item = &Creature> + '_ {
+        self.creatures.iter()
+    }
+
+    pub fn field_at(&self, point: Point) -> Option<PlaneField> {
+        self.plane
+            .get(point.1 as usize)
+            .and_then(|row| row.get(point.0 as usize))
+            .copied()
+    }
+
+    pub fn game_id(&self) -> u64 {
+        self.game_id
+    }
+
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    // Every tick and purpose gets its own generator derived from the game seed,
+    // so a seeded game replays identically for the same inputs.
+    fn tick_rng(&self, stream: u64) -> StdRng {
+        tick_rng(self.seed, self.tick, stream)
+    }
+
+    // With fog of war the snapshot lists only what is in sight right now;
+    // apples and walls remembered from earlier ticks may be stale and are
+    // listed separately.
+    pub fn snapshot(&self) -> GameSnapshot {
+        GameSnapshot {
+            tick: self.tick,
+            width: self.config.board.width,
+            height: self.config.board.height,
+            snake: self.snake_segments().collect(),
+            apples: self.visible_points(PlaneField::Apple),
+            walls: self.visible_points(PlaneField::Wall),
+            creatures: self.visible_points(PlaneField::Creature),
+            remembered_apples: self.remembered_points(PlaneField::Apple),
+            remembered_walls: self.remembered_points(PlaneField::Wall),
+            direction: self.direction,
+            score: self.score(),
+            lives_left: self.lives_left,
+        }
+    }
+
+    fn visible_points(&self, field: PlaneField) -> Vec<Point> {
+        let mut points = match (field, self.config.visibility) {
+            (PlaneField::Apple, None) => self.apples().collect::<Vec<Point>>(),
+            (PlaneField::Creature, None) => self
+                .creatures()
+                .map(|creature| creature.position)
+                .collect::<Vec<Point>>(),
+            (_, None) => self.walls().collect::<Vec<Point>>(),
+            (_, Some(_)) => self
+                .board_points()
+                .filter(|&point| self.visible_cell(point) == VisibleCell::Visible(field))
+                .collect(),
         };
-        if self.check_for_game_over(new_head) {
-            self.new_game();
-            return;
+        points.sort();
+        points
+    }
+
+    fn remembered_points(&self, field: PlaneField) -> Vec<Point> {
+        if self.config.visibility.is_none() {
+            return Vec::new();
+        }
+        let mut points = self
+            .board_points()
+            .filter(|&point| self.visible_cell(point) == VisibleCell::Remembered(field))
+            .collect::<Vec<Point>>();
+        points.sort();
+        points
+    }
+
+    // The snake's own body is always visible, everything else only within the
+    // visibility radius around the head or as remembered from earlier ticks.
+    pub fn visible_cell(&self, point: Point) -> VisibleCell {
+        let field = match self.field_at(point) {
+            Some(field) => field,
+            None => return VisibleCell::Hidden,
+        };
+        let visibility = match self.config.visibility {
+            Some(visibility) => visibility,
+            None => return VisibleCell::Visible(field),
+        };
+        if field == PlaneField::Body || self.is_revealed(&visibility, point) {
+            return VisibleCell::Visible(field);
         }
+        match self.remembered[point.1 as usize][point.0 as usize] {
+            Some(remembered) => VisibleCell::Remembered(remembered),
+            None => VisibleCell::Hidden,
+        }
+    }
 
-        self.handle_collisions_with_apples(new_head);
-        self.snake.push_front(new_head);
+    fn is_revealed(&self, visibility: &VisibilityConfig, point: Point) -> bool {
+        match self.snake.front() {
+            Some(&head) => visibility.reveals(head, point, &self.config.board),
+            None => false,
+        }
+    }
+
+    fn remember_visible_cells(&mut self) {
+        let visibility = match self.config.visibility {
+            Some(visibility) => visibility,
+            None => return,
+        };
+        let revealed = self
+            .board_points()
+            .filter(|&point| self.is_revealed(&visibility, point))
+            .collect::<Vec<Point>>();
+        for Point(x, y) in revealed {
+            let field = match self.plane[y as usize][x as usize] {
+                PlaneField::Body | PlaneField::Creature => PlaneField::Empty,
+                field => field,
+            };
+            self.remembered[y as usize][x as usize] = Some(field);
+        }
+    }
+
+    pub fn stats(&self) -> GameStats {
+        self.stats
+    }
+
+    pub fn lives_left(&self) -> u32 {
+        self.lives_left
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerable_ticks_left > 0
+    }
+
+    pub fn tick_interval_milliseconds(&self) -> u64 {
+        self.config
+            .speed_policy
+            .interval_milliseconds(self.snake.len(), self.score())
+    }
+
+    pub fn update_position(&mut self) -> TickResult {
+        self.tick += 1;
+        let game_over = self.advance_game();
+
+        let snapshot = self.snapshot();
+        let delta = StateDelta::between(&self.tick_snapshot, &snapshot);
+        self.tick_snapshot = snapshot;
+        TickResult { game_over, delta }
+    }
 
-        self.insert_apple_by_random();
+    fn advance_game(&mut self) -> Option<GameResult> {
+        if self.ticks_until_next_shrink() == Some(1) {
+            self.shrink_arena();
+        }
+        self.stats.ticks += 1;
+        if self.is_snake_caught_in_walls()
+            && self.rules.on_collision(self, DeathCause::CaughtByArena) == CollisionOutcome::Die
+        {
+            return self.lose_life(DeathCause::CaughtByArena);
+        }
+        self.move_creatures();
+
+        let rules = Arc::clone(&self.rules);
+        let new_head = match self.next_step() {
+            Step::Move(new_head) => new_head,
+            Step::Stay => {
+                self.update_plane();
+                return None;
+            }
+            Step::Die(cause) => return self.lose_life(cause),
+        };
+
+        let tail = self.snake.pop_back();
+        if let Some(cause) = self.fatal_collision(self.detect_collision(new_head)) {
+            // The snake died before moving, so it keeps its full length.
+            self.snake.extend(tail);
+            return self.lose_life(cause);
+        }
+        self.move_head(new_head);
+        if self.is_snake_touching_creature()
+            && rules.on_collision(self, DeathCause::CreatureContact) == CollisionOutcome::Die
+        {
+            return self.lose_life(DeathCause::CreatureContact);
+        }
+        self.notify(GameEvent::SnakeMoved {
+            head: new_head,
+            length: self.snake.len(),
+        });
+
+        self.spawn_apple();
+        if self.config.mode.is_hazard_due(&self.stats) {
+            self.insert_hazard_by_random();
+        }
+        self.spawn_creature();
         self.update_plane();
+
+        if let Some(reason) = rules.after_move(self) {
+            return Some(self.end_game(reason));
+        }
+        let reason = self
+            .config
+            .mode
+            .end_condition(&self.stats, self.snake.len())?;
+        Some(self.end_game(reason))
     }
 
-    fn handle_collisions_with_apples(&mut self, head: Point) {
-        let collides_with_apple = self.apples.iter().find(|&&apple| apple == head).is_some();
-        if collides_with_apple {
-            self.apples.remove(&head.clone());
-            self.append_snake();
+    fn next_step(&self) -> Step {
+        let rules = Arc::clone(&self.rules);
+        // Leaving a walled board counts as hitting a wall.
+        if self
+            .config
+            .board
+            .step(self.head(), self.direction)
+            .is_none()
+        {
+            return match rules.on_collision(self, DeathCause::WallCollision) {
+                CollisionOutcome::Die => Step::Die(DeathCause::WallCollision),
+                CollisionOutcome::Ignore => Step::Stay,
+            };
+        }
+        match rules.before_move(self, self.head(), self.direction) {
+            Some(new_head) => Step::Move(new_head),
+            None => Step::Stay,
         }
     }
 
-    fn check_for_game_over(&mut self, head: Point) -> bool {
-        self.snake.iter().find(|&&body| body == head).is_some()
+    fn fatal_collision(&self, cause: Option<DeathCause>) -> Option<DeathCause> {
+        let rules = Arc::clone(&self.rules);
+        cause.filter(|&cause| rules.on_collision(self, cause) == CollisionOutcome::Die)
     }
 
-    fn insert_apple_by_random(&mut self) {
-        let mut rng = rand::thread_rng();
-        let random_x = Uniform::from(0..PLANE_WIDTH - 1);
-        let random_y = Uniform::from(0..PLANE_HEIGHT - 1);
+    // Expects the tail to be popped already, as apples grow the snake from there.
+    fn move_head(&mut self, new_head: Point) {
+        self.invulnerable_ticks_left = self.invulnerable_ticks_left.saturating_sub(1);
+        self.handle_collisions_with_apples(new_head);
+        self.snake.push_front(new_head);
+        self.update_longest_length();
+    }
+
+    fn detect_collision(&self, head: Point) -> Option<DeathCause> {
+        if self.walls.contains(&head) {
+            Some(DeathCause::WallCollision)
+        } else if self.snake.contains(&head) {
+            Some(DeathCause::SelfCollision)
+        } else {
+            None
+        }
+    }
+
+    fn handle_collisions_with_apples(&mut self, head: Point) {
+        if !self.apples.contains(&head) {
+            return;
+        }
+
+        let rules = Arc::clone(&self.rules);
+        if let Some(growth) = rules.on_eat(self, head) {
+            self.apples.remove(&head);
+            for _ in 0..growth {
+                self.append_snake();
+            }
+            self.stats.apples_eaten += 1;
+            self.notify(GameEvent::AppleEaten(head));
+        }
+    }
 
-        let random_apple = Uniform::from(0..(1. / APPLE_GENERATION_CHANCE_PER_MOVE) as i32);
+    fn spawn_apple(&mut self) {
+        let rules = Arc::clone(&self.rules);
+        if let Some(apple) = rules.on_spawn(self, &mut self.tick_rng(APPLE_RNG_STREAM)) {
+            if self.insert_apple(apple).is_ok() {
+                self.notify(GameEvent::AppleSpawned(apple));
+            }
+        }
+    }
 
-        if random_apple.sample(&mut rng) == 0 {
-            let _ = self.insert_apple(Point(random_x.sample(&mut rng), random_y.sample(&mut rng)));
+    fn insert_hazard_by_random(&mut self) {
+        let free_fields = self.free_fields_away_from_head();
+        if let Some(&hazard) = free_fields.choose(&mut self.tick_rng(HAZARD_RNG_STREAM)) {
+            self.walls.insert(hazard);
         }
     }
 
+    fn free_fields_away_from_head(&self) -> Vec<Point> {
+        let Point(head_x, head_y) = *self.snake.front().expect("Snake should not be empty");
+        self.board_points()
+            .filter(|&Point(x, y)| head_x.abs_diff(x) + head_y.abs_diff(y) > HAZARD_HEAD_CLEARANCE)
+            .filter(|&field| {
+                !self.snake.contains(&field)
+                    && !self.apples.contains(&field)
+                    && !self.walls.contains(&field)
+                    && !self.is_creature_at(field)
+            })
+            .collect()
+    }
+
     fn insert_apple(&mut self, apple_position: Point) -> Result<(), AppleInsertError> {
-        match apple_position {
-            Point(x, _) if x >= PLANE_WIDTH => return Err(AppleInsertError),
-            Point(_, y) if y >= PLANE_HEIGHT => return Err(AppleInsertError),
-            Point(_, _) => (),
-        };
+        if !self.config.board.contains(apple_position) {
+            return Err(AppleInsertError);
+        }
 
         let collides_with_snake = self
             .snake
@@ -189,7 +836,14 @@ impl SnakeContext {
             .iter()
             .any(|&existing_apple| existing_apple == apple_position);
 
-        if collides_with_snake || collides_with_other_apples {
+        let collides_with_walls = self.walls.contains(&apple_position);
+        let collides_with_creatures = self.is_creature_at(apple_position);
+
+        if collides_with_snake
+            || collides_with_other_apples
+            || collides_with_walls
+            || collides_with_creatures
+        {
             return Err(AppleInsertError);
         }
 
@@ -199,6 +853,10 @@ impl SnakeContext {
 
     fn update_plane(&mut self) {
         self.clear_plane();
+        for wall in &self.walls {
+            self.plane[wall.1 as usize][wall.0 as usize] = PlaneField::Wall;
+        }
+
         for point in &self.snake {
             self.plane[point.1 as usize][point.0 as usize] = PlaneField::Body;
         }
@@ -206,10 +864,21 @@ impl SnakeContext {
         for apple in &self.apples {
             self.plane[apple.1 as usize][apple.0 as usize] = PlaneField::Apple;
         }
+
+        for creature in &self.creatures {
+            let Point(x, y) = creature.position;
+            self.plane[y as usize][x as usize] = PlaneField::Creature;
+        }
+        self.remember_visible_cells();
     }
 
     fn clear_plane(&mut self) {
-        self.plane = [[PlaneField::Empty; PLANE_WIDTH as usize]; PLANE_HEIGHT as usize];
+        self.plane = board_cells(&self.config.board, PlaneField::Empty);
+    }
+
+    fn board_points(&self) -> impl Iterator<Item = Point> {
+        let BoardConfig { width, height, .. } = self.config.board;
+        (0..height).flat_map(move |y| (0..width).map(move |x| Point(x, y)))
     }
 
     fn append_snake(&mut self) {
@@ -223,63 +892,93 @@ impl SnakeContext {
             .collect::<Vec<Point>>();
 
         let last_two_elements_are_placed_horizontally = last_two[1].1 == last_two[0].1;
-        if last_two_elements_are_placed_horizontally {
-            let y = last_two[1].1;
-            let should_append_right = last_two[1].0 as i32 > last_two[0].0 as i32;
-            if should_append_right {
-                let is_on_right_wall = last_two[1].0 == PLANE_WIDTH - 1;
-                let x = if is_on_right_wall {
-                    0
-                } else {
-                    last_two[1].0 + 1
-                };
-                self.snake.push_back(Point(x, y))
+        let direction = if last_two_elements_are_placed_horizontally {
+            if last_two[1].0 > last_two[0].0 {
+                Direction::Right
             } else {
-                let is_on_left_wall = last_two[1].0 == 0;
-                let x = if is_on_left_wall {
-                    PLANE_WIDTH - 1
-                } else {
-                    last_two[1].0 - 1
-                };
-                self.snake.push_back(Point(x, y))
+                Direction::Left
             }
+        } else if last_two[1].1 > last_two[0].1 {
+            Direction::Down
         } else {
-            let x = last_two[1].0;
-            let should_append_down = last_two[1].1 as i32 > last_two[0].1 as i32;
-            if should_append_down {
-                let is_on_bottom_wall = last_two[1].1 == PLANE_HEIGHT - 1;
-                let y = if is_on_bottom_wall {
-                    0
-                } else {
-                    last_two[1].1 + 1
-                };
-                self.snake.push_back(Point(x, y))
-            } else {
-                let is_on_top_wall = last_two[1].1 == 0;
-                let y = if is_on_top_wall {
-                    PLANE_HEIGHT - 1
-                } else {
-                    last_two[1].1 - 1
-                };
-                self.snake.push_back(Point(x, y))
-            }
-        }
+            Direction::Up
+        };
+        // On a walled board a tail at the edge grows in place.
+        let tail = self
+            .config
+            .board
+            .step(last_two[1], direction)
+            .unwrap_or(last_two[1]);
+        self.snake.push_back(tail);
     }
 }
 
+// A fresh seed for callers that need one before any game exists.
+#[cfg(feature = "std")]
+pub fn random_seed() -> u64 {
+    rand::thread_rng().next_u64()
+}
+
+pub(crate) fn tick_rng(seed: u64, tick: u64, stream: u64) -> StdRng {
+    let tick_seed = tick.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    StdRng::seed_from_u64(seed ^ tick_seed ^ stream.rotate_left(32))
+}
+
+fn board_cells<T: Clone>(board: &BoardConfig, value: T) -> Vec<Vec<T>> {
+    vec![vec![value; board.width as usize]; board.height as usize]
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
+        creature::{CreatureBehavior, CreatureSpawning},
+        game_config::{
+            board::{BoardConfig, Topology},
+            speed_policy::SpeedPolicy,
+            GameConfig, ShrinkingArenaConfig,
+        },
+        game_mode::{DeathCause, GameMode, GameOverReason},
+        game_observer::{GameEvent, GameObserver},
+        level_map::LevelMap,
+        rule_set::RuleSet,
+        visibility::{DistanceMetric, VisibilityConfig, VisibleCell},
         AppleInsertError, Direction, DirectionChangeError, PlaneField, Point, SnakeContext,
     };
 
+    use std::sync::{Arc, Mutex};
+
     const PLANE_WIDTH: usize = 20;
     const PLANE_HEIGHT: usize = 10;
 
+    #[derive(Debug, Default)]
+    struct RecordingObserver(Arc<Mutex<Vec<GameEvent>>>);
+
+    impl GameObserver for RecordingObserver {
+        fn on_event(&mut self, event: &GameEvent) {
+            self.0.lock().unwrap().push(*event);
+        }
+    }
+
+    #[derive(Debug)]
+    struct GreedyFrozenRuleSet;
+
+    impl RuleSet for GreedyFrozenRuleSet {
+        fn before_move(&self, game: &SnakeContext, head: Point, _: Direction) -> Option<Point> {
+            if game.stats().ticks > 1 {
+                return None;
+            }
+            Some(Point(head.0 - 1, head.1))
+        }
+
+        fn on_eat(&self, _: &SnakeContext, _: Point) -> Option<u32> {
+            Some(3)
+        }
+    }
+
     #[test]
     fn test_should_create_empty_20_10_plane_after_initialization() {
         let game = SnakeContext::new();
-        assert_eq!([[PlaneField::Empty; PLANE_WIDTH]; PLANE_HEIGHT], game.plane);
+        assert_eq!(game.plane, [[PlaneField::Empty; PLANE_WIDTH]; PLANE_HEIGHT]);
     }
 
     #[test]
@@ -291,15 +990,15 @@ mod tests {
         let initial_pos = (10usize, 5usize);
         assert_eq!(
             PlaneField::Body,
-            game.plane[initial_pos.1 as usize][initial_pos.0]
+            game.plane[initial_pos.1][initial_pos.0]
         );
         assert_eq!(
             PlaneField::Body,
-            game.plane[initial_pos.1 as usize][initial_pos.0 + 1]
+            game.plane[initial_pos.1][initial_pos.0 + 1]
         );
         assert_eq!(
             PlaneField::Body,
-            game.plane[initial_pos.1 as usize][initial_pos.0 + 2]
+            game.plane[initial_pos.1][initial_pos.0 + 2]
         );
         assert_eq!(Direction::Left, game.direction);
     }
@@ -313,15 +1012,15 @@ mod tests {
         game.update_plane();
         assert_eq!(
             PlaneField::Body,
-            game.plane[initial_pos.1 as usize][initial_pos.0 - 1]
+            game.plane[initial_pos.1][initial_pos.0 - 1]
         );
         assert_eq!(
             PlaneField::Body,
-            game.plane[initial_pos.1 as usize][initial_pos.0]
+            game.plane[initial_pos.1][initial_pos.0]
         );
         assert_eq!(
             PlaneField::Body,
-            game.plane[initial_pos.1 as usize][initial_pos.0 + 1]
+            game.plane[initial_pos.1][initial_pos.0 + 1]
         );
     }
 
@@ -338,7 +1037,7 @@ mod tests {
         let mut game = SnakeContext::new();
         game.new_game();
         let result = game.change_direction(Direction::Up);
-        assert_eq!(true, result.is_ok());
+        assert!(result.is_ok());
     }
 
     #[test]
@@ -364,7 +1063,7 @@ mod tests {
             game.update_position();
         }
         let result = game.change_direction(Direction::Left);
-        assert_eq!(true, result.is_ok());
+        assert!(result.is_ok());
     }
 
     #[test]
@@ -378,15 +1077,15 @@ mod tests {
         game.update_plane();
         assert_eq!(
             PlaneField::Body,
-            game.plane[initial_pos.1 as usize - 1][initial_pos.0]
+            game.plane[initial_pos.1 - 1][initial_pos.0]
         );
         assert_eq!(
             PlaneField::Body,
-            game.plane[initial_pos.1 as usize][initial_pos.0]
+            game.plane[initial_pos.1][initial_pos.0]
         );
         assert_eq!(
             PlaneField::Body,
-            game.plane[initial_pos.1 as usize][initial_pos.0 + 1]
+            game.plane[initial_pos.1][initial_pos.0 + 1]
         );
     }
 
@@ -402,15 +1101,15 @@ mod tests {
         let expected_pos = (0usize, 5usize);
         assert_eq!(
             PlaneField::Body,
-            game.plane[expected_pos.1 as usize][PLANE_WIDTH - 1]
+            game.plane[expected_pos.1][PLANE_WIDTH - 1]
         );
         assert_eq!(
             PlaneField::Body,
-            game.plane[expected_pos.1 as usize][expected_pos.0]
+            game.plane[expected_pos.1][expected_pos.0]
         );
         assert_eq!(
             PlaneField::Body,
-            game.plane[expected_pos.1 as usize][expected_pos.0 + 1]
+            game.plane[expected_pos.1][expected_pos.0 + 1]
         );
     }
 
@@ -432,11 +1131,36 @@ mod tests {
         );
         assert_eq!(
             PlaneField::Body,
-            game.plane[expected_pos.1 as usize][expected_pos.0]
+            game.plane[expected_pos.1][expected_pos.0]
         );
         assert_eq!(
             PlaneField::Body,
-            game.plane[expected_pos.1 + 1 as usize][expected_pos.0]
+            game.plane[expected_pos.1 + 1][expected_pos.0]
+        );
+    }
+
+    #[test]
+    fn test_should_lose_life_when_snake_leaves_walled_board() {
+        let mut game = SnakeContext::with_config(GameConfig {
+            board: BoardConfig {
+                width: 8,
+                height: 5,
+                topology: Topology::Walled,
+            },
+            apple_spawn_one_in: 0,
+            ..GameConfig::default()
+        });
+        game.new_game();
+        assert_eq!(Point(4, 2), game.head());
+        assert_eq!((8, 5), (game.snapshot().width, game.snapshot().height));
+
+        let results = (0..5)
+            .map(|_| game.update_position().game_over)
+            .collect::<Vec<_>>();
+        assert!(results[..4].iter().all(Option::is_none));
+        assert_eq!(
+            GameOverReason::Died(DeathCause::WallCollision),
+            results[4].expect("game should be over").reason
         );
     }
 
@@ -499,7 +1223,7 @@ mod tests {
         let mut game = SnakeContext::new();
         game.new_game();
         let result = game.insert_apple(Point(0, 0));
-        assert_eq!(true, result.is_ok());
+        assert!(result.is_ok());
     }
 
     #[test]
@@ -535,4 +1259,454 @@ mod tests {
             game.plane[initial_pos.1 as usize][initial_pos.0 as usize + 2]
         );
     }
+
+    #[test]
+    fn test_should_shorten_tick_interval_when_snake_grows_with_length_based_speed_policy() {
+        let mut game = SnakeContext::with_config(GameConfig {
+            speed_policy: SpeedPolicy::LengthBased {
+                initial_interval_milliseconds: 1000,
+                decrease_per_segment_milliseconds: 100,
+                minimum_interval_milliseconds: 200,
+            },
+            ..GameConfig::default()
+        });
+        game.new_game();
+        assert_eq!(1000, game.tick_interval_milliseconds());
+        game.insert_apple(Point(9, 5))
+            .expect("it should be ok in this case");
+        game.update_position();
+        assert_eq!(1, game.score());
+        assert_eq!(900, game.tick_interval_milliseconds());
+    }
+
+    fn grow_snake_to_length_5_and_bite_own_body(game: &mut SnakeContext) {
+        game.insert_apple(Point(9, 5))
+            .expect("it should be ok in this case");
+        game.insert_apple(Point(8, 5))
+            .expect("it should be ok in this case");
+        game.update_position();
+        game.update_position();
+        for direction in [Direction::Up, Direction::Right, Direction::Down] {
+            game.change_direction(direction)
+                .expect("Directions should be ok in this scenario");
+            game.update_position();
+        }
+    }
+
+    #[test]
+    fn test_should_start_new_game_when_snake_collides_with_itself_and_only_one_life() {
+        let mut game = SnakeContext::new();
+        game.new_game();
+        grow_snake_to_length_5_and_bite_own_body(&mut game);
+        assert_eq!(0, game.score());
+        assert_eq!(0, game.stats().deaths);
+        assert_eq!(3, game.snake.len());
+    }
+
+    #[test]
+    fn test_should_report_full_length_when_snake_bites_itself() {
+        let mut game = SnakeContext::new();
+        game.new_game();
+        grow_snake_to_length_5_and_bite_own_body(&mut game);
+        let result = game.last_game_result().expect("game should be over");
+        assert_eq!(GameOverReason::Died(DeathCause::SelfCollision), result.reason);
+        assert_eq!(5, result.final_length);
+    }
+
+    #[test]
+    fn test_should_respawn_snake_keeping_score_and_apples_when_lives_left() {
+        let mut game = SnakeContext::with_config(GameConfig {
+            lives: 2,
+            ..GameConfig::default()
+        });
+        game.new_game();
+        game.insert_apple(Point(0, 0))
+            .expect("it should be ok in this case");
+        grow_snake_to_length_5_and_bite_own_body(&mut game);
+        assert_eq!(1, game.lives_left());
+        assert_eq!(2, game.score());
+        assert_eq!(1, game.stats().deaths);
+        assert!(game.is_invulnerable());
+        assert!(game.apples.contains(&Point(0, 0)));
+        assert_eq!(
+            vec![Point(10, 5), Point(11, 5), Point(12, 5)],
+            game.snake.iter().cloned().collect::<Vec<Point>>()
+        );
+    }
+
+    #[test]
+    fn test_should_respawn_snake_away_from_apples_when_initial_position_is_taken() {
+        let mut game = SnakeContext::with_config(GameConfig {
+            lives: 2,
+            ..GameConfig::default()
+        });
+        game.new_game();
+        game.insert_apple(Point(9, 5))
+            .expect("it should be ok in this case");
+        game.insert_apple(Point(8, 5))
+            .expect("it should be ok in this case");
+        game.update_position();
+        game.update_position();
+        game.change_direction(Direction::Up)
+            .expect("Directions should be ok in this scenario");
+        game.update_position();
+        game.insert_apple(Point(12, 5))
+            .expect("it should be ok in this case");
+        for direction in [Direction::Right, Direction::Down] {
+            game.change_direction(direction)
+                .expect("Directions should be ok in this scenario");
+            game.update_position();
+        }
+        assert_eq!(1, game.lives_left());
+        assert!(!game.snake.contains(&Point(12, 5)));
+        assert!(!game.snake.contains(&Point(9, 5)));
+    }
+
+    fn shrinking_arena_game(shrink_interval_ticks: u64) -> SnakeContext {
+        let mut game = SnakeContext::with_config(GameConfig {
+            shrinking_arena: Some(ShrinkingArenaConfig {
+                shrink_interval_ticks,
+            }),
+            ..GameConfig::default()
+        });
+        game.new_game();
+        game
+    }
+
+    #[test]
+    fn test_should_turn_outer_ring_into_walls_and_remove_apples_there_when_shrink_is_due() {
+        let mut game = shrinking_arena_game(2);
+        game.insert_apple(Point(0, 3))
+            .expect("it should be ok in this case");
+        assert_eq!(Some(2), game.ticks_until_next_shrink());
+        game.update_position();
+        assert_eq!(Some(1), game.ticks_until_next_shrink());
+        assert_eq!(PlaneField::Empty, game.plane[0][0]);
+        game.update_position();
+        assert_eq!(Some(2), game.ticks_until_next_shrink());
+        assert_eq!(PlaneField::Wall, game.plane[0][0]);
+        assert_eq!(PlaneField::Wall, game.plane[PLANE_HEIGHT - 1][7]);
+        assert_eq!(PlaneField::Wall, game.plane[3][PLANE_WIDTH - 1]);
+        assert_eq!(PlaneField::Empty, game.plane[1][1]);
+        assert!(!game.apples.contains(&Point(0, 3)));
+        assert!(game.insert_apple(Point(0, 4)).is_err());
+    }
+
+    #[test]
+    fn test_should_lose_life_when_snake_is_caught_inside_closing_ring() {
+        let mut game = SnakeContext::with_config(GameConfig {
+            lives: 2,
+            shrinking_arena: Some(ShrinkingArenaConfig {
+                shrink_interval_ticks: 1,
+            }),
+            ..GameConfig::default()
+        });
+        game.new_game();
+        game.snake = [Point(0, 5), Point(1, 5), Point(2, 5)].into_iter().collect();
+        game.update_position();
+        assert_eq!(1, game.lives_left());
+        assert!(game.snake.iter().all(|body| !game.walls.contains(body)));
+    }
+
+    #[test]
+    fn test_should_stop_shrinking_when_arena_reaches_minimal_size() {
+        let mut game = shrinking_arena_game(1);
+        for _ in 0..4 {
+            game.update_position();
+        }
+        assert_eq!(None, game.ticks_until_next_shrink());
+        assert_eq!(PlaneField::Wall, game.plane[3][10]);
+        assert_eq!(PlaneField::Wall, game.plane[6][10]);
+        assert_eq!(PlaneField::Body, game.plane[5][6]);
+    }
+
+    #[test]
+    fn test_should_report_game_over_with_death_cause_when_last_life_lost() {
+        let mut game = SnakeContext::new();
+        game.new_game();
+        game.insert_apple(Point(9, 5))
+            .expect("it should be ok in this case");
+        game.insert_apple(Point(8, 5))
+            .expect("it should be ok in this case");
+        game.update_position();
+        game.update_position();
+        let mut results = vec![];
+        for direction in [Direction::Up, Direction::Right, Direction::Down] {
+            game.change_direction(direction)
+                .expect("Directions should be ok in this scenario");
+            results.push(game.update_position());
+        }
+        let result = results[2].game_over.expect("game should be over");
+        assert_eq!(GameOverReason::Died(DeathCause::SelfCollision), result.reason);
+        assert_eq!(GameMode::Endless, result.mode);
+        assert_eq!(2, result.score);
+        assert_eq!(Some(result), game.last_game_result());
+    }
+
+    #[test]
+    fn test_should_end_time_limited_game_with_longest_length_as_score_when_budget_runs_out() {
+        let mut game = SnakeContext::with_config(GameConfig {
+            mode: GameMode::TimeLimited { tick_budget: 3 },
+            ..GameConfig::default()
+        });
+        game.new_game();
+        game.insert_apple(Point(9, 5))
+            .expect("it should be ok in this case");
+        assert_eq!(None, game.update_position().game_over);
+        assert_eq!(None, game.update_position().game_over);
+        let result = game
+            .update_position()
+            .game_over
+            .expect("game should be over");
+        assert_eq!(GameOverReason::TimeUp, result.reason);
+        assert_eq!(4, result.score);
+        assert_eq!(0, game.stats().ticks);
+    }
+
+    #[test]
+    fn test_should_spawn_hazards_over_time_when_survival_mode() {
+        let mut game = SnakeContext::with_config(GameConfig {
+            mode: GameMode::Survival {
+                hazard_interval_ticks: 2,
+            },
+            ..GameConfig::default()
+        });
+        game.new_game();
+        game.update_position();
+        assert!(game.walls.is_empty());
+        game.update_position();
+        assert_eq!(1, game.walls.len());
+        game.update_position();
+        game.update_position();
+        assert_eq!(2, game.walls.len());
+        assert_eq!(GameMode::Survival { hazard_interval_ticks: 2 }, game.game_mode());
+    }
+
+    #[test]
+    fn test_should_apply_custom_rule_set_hooks_when_provided() {
+        let mut game =
+            SnakeContext::with_rule_set(GameConfig::default(), Arc::new(GreedyFrozenRuleSet));
+        game.new_game();
+        game.insert_apple(Point(9, 5))
+            .expect("it should be ok in this case");
+        game.update_position();
+        assert_eq!(6, game.snake.len());
+        assert_eq!(Point(9, 5), game.head());
+        game.update_position();
+        assert_eq!(6, game.snake.len());
+        assert_eq!(Point(9, 5), game.head());
+    }
+
+    #[test]
+    fn test_should_notify_registered_observers_with_typed_events() {
+        let events = Arc::new(Mutex::new(vec![]));
+        let mut game = SnakeContext::new();
+        game.register_observer(Box::new(RecordingObserver(events.clone())));
+        game.new_game();
+        game.insert_apple(Point(9, 5))
+            .expect("it should be ok in this case");
+        game.update_position();
+        let _ = game.change_direction(Direction::Right);
+        game.change_direction(Direction::Up)
+            .expect("Directions should be ok in this scenario");
+
+        let events = events.lock().unwrap();
+        assert_eq!(GameEvent::GameStarted, events[0]);
+        assert_eq!(GameEvent::AppleEaten(Point(9, 5)), events[1]);
+        assert_eq!(
+            GameEvent::SnakeMoved {
+                head: Point(9, 5),
+                length: 4
+            },
+            events[2]
+        );
+        assert!(events.contains(&GameEvent::DirectionRejected {
+            current: Direction::Left,
+            requested: Direction::Right
+        }));
+        assert_eq!(
+            Some(&GameEvent::DirectionChanged {
+                from: Direction::Left,
+                to: Direction::Up
+            }),
+            events.last()
+        );
+    }
+
+    #[test]
+    fn test_should_notify_game_over_before_next_game_started() {
+        let events = Arc::new(Mutex::new(vec![]));
+        let mut game = SnakeContext::new();
+        game.new_game();
+        game.register_observer(Box::new(RecordingObserver(events.clone())));
+        game.snake = [Point(9, 5), Point(10, 5), Point(11, 5)].into_iter().collect();
+        game.walls.insert(Point(8, 5));
+        let result = game.update_position().game_over.expect("game should be over");
+
+        let events = events.lock().unwrap();
+        assert_eq!(
+            vec![GameEvent::GameOver(result), GameEvent::GameStarted],
+            *events
+        );
+    }
+
+    #[test]
+    fn test_should_assign_next_game_id_when_game_restarts() {
+        let mut game = SnakeContext::new();
+        assert_eq!(0, game.game_id());
+        game.new_game();
+        assert_eq!(1, game.game_id());
+        game.walls.insert(Point(9, 5));
+        game.update_position();
+        assert_eq!(2, game.game_id());
+    }
+
+    #[test]
+    fn test_should_keep_snapshot_in_sync_when_applying_deltas_from_tick_results() {
+        let mut game = SnakeContext::with_config(GameConfig {
+            lives: 2,
+            mode: GameMode::Survival {
+                hazard_interval_ticks: 3,
+            },
+            ..GameConfig::default()
+        });
+        game.new_game();
+        let mut synced = game.snapshot();
+        game.insert_apple(Point(9, 5))
+            .expect("it should be ok in this case");
+        for tick in 0..60 {
+            if tick % 7 == 0 {
+                let _ = game.change_direction(Direction::Up);
+            } else if tick % 7 == 3 {
+                let _ = game.change_direction(Direction::Left);
+            }
+            let result = game.update_position();
+            synced
+                .apply_delta(&result.delta)
+                .expect("deltas should apply without gaps");
+            assert_eq!(game.snapshot(), synced);
+        }
+        assert_eq!(61, synced.tick);
+    }
+
+    #[test]
+    fn test_should_replay_identical_game_when_same_seed_and_inputs() {
+        let play = |seed| {
+            let mut game = SnakeContext::with_config(GameConfig {
+                mode: GameMode::Survival {
+                    hazard_interval_ticks: 5,
+                },
+                seed: Some(seed),
+                ..GameConfig::default()
+            });
+            game.new_game();
+            (0..200)
+                .map(|_| {
+                    game.update_position();
+                    game.snapshot()
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(play(7), play(7));
+        assert_ne!(play(7), play(8));
+    }
+
+    #[test]
+    fn test_should_hide_cells_outside_radius_and_remember_seen_ones_when_fog_of_war() {
+        let mut game = SnakeContext::with_config(GameConfig {
+            visibility: Some(VisibilityConfig {
+                radius: 2,
+                metric: DistanceMetric::Manhattan,
+            }),
+            seed: Some(1),
+            ..GameConfig::default()
+        });
+        game.new_game();
+        game.insert_apple(Point(6, 5))
+            .expect("it should be ok in this case");
+        game.update_plane();
+
+        assert_eq!(VisibleCell::Hidden, game.visible_cell(Point(6, 5)));
+        assert_eq!(
+            VisibleCell::Visible(PlaneField::Body),
+            game.visible_cell(Point(12, 5))
+        );
+        assert!(!game.snapshot().apples.contains(&Point(6, 5)));
+        assert!(game.get_plane_string().starts_with('?'));
+
+        game.update_position();
+        game.update_position();
+        assert_eq!(
+            VisibleCell::Visible(PlaneField::Apple),
+            game.visible_cell(Point(6, 5))
+        );
+
+        game.change_direction(Direction::Up)
+            .expect("it should be ok in this case");
+        for _ in 0..3 {
+            game.update_position();
+        }
+        assert_eq!(
+            VisibleCell::Remembered(PlaneField::Apple),
+            game.visible_cell(Point(6, 5))
+        );
+        assert!(!game.snapshot().apples.contains(&Point(6, 5)));
+        assert!(game.snapshot().remembered_apples.contains(&Point(6, 5)));
+        assert_eq!(
+            Some('o'),
+            game.get_plane_string()
+                .lines()
+                .nth(5)
+                .and_then(|row| row.chars().nth(6))
+        );
+    }
+
+    #[test]
+    fn test_should_lose_life_when_creature_from_level_touches_snake() {
+        let mut game = SnakeContext::with_config(GameConfig {
+            level: Some(LevelMap::parse("\n\n\n\n\n........>").expect("level should parse")),
+            ..GameConfig::default()
+        });
+        game.new_game();
+        assert_eq!(PlaneField::Creature, game.plane[5][8]);
+
+        let result = game
+            .update_position()
+            .game_over
+            .expect("game should be over");
+        assert_eq!(
+            GameOverReason::Died(DeathCause::CreatureContact),
+            result.reason
+        );
+    }
+
+    #[test]
+    fn test_should_spawn_and_move_creatures_deterministically_when_seeded() {
+        let config = GameConfig {
+            creature_spawning: Some(CreatureSpawning {
+                interval_ticks: 2,
+                behavior: CreatureBehavior::RandomWalk,
+                max_creatures: 1,
+            }),
+            lives: 100,
+            seed: Some(11),
+            ..GameConfig::default()
+        };
+        let play = || {
+            let mut game = SnakeContext::with_config(config.clone());
+            game.new_game();
+            (0..12)
+                .map(|_| {
+                    game.update_position();
+                    game.snapshot().creatures
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let positions = play();
+        assert!(positions[0].is_empty());
+        assert!(positions[1..].iter().all(|creatures| creatures.len() == 1));
+        assert_ne!(positions[1], positions[11]);
+        assert_eq!(positions, play());
+    }
 }
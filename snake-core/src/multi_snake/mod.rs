@@ -0,0 +1,599 @@
+use alloc::boxed::Box;
+use alloc::collections::{BTreeSet, VecDeque};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use rand::seq::SliceRandom;
+use rand::RngCore;
+
+use crate::game_config::board::BoardConfig;
+use crate::game_config::GameConfig;
+use crate::game_mode::{DeathCause, GameOverReason};
+use crate::game_observer::{GameEvent, GameObserver};
+use crate::rule_set::RuleSet;
+use crate::{
+    tick_rng, Direction, DirectionChangeError, GameStats, Point, SnakeContext, Step,
+    APPLE_RNG_STREAM, HAZARD_RNG_STREAM,
+};
+
+// Each player's own context keeps its lives, stats and respawn invulnerability
+// and is the one handed to the rule set and observers.
+#[derive(Debug)]
+pub struct PlayerSnake {
+    game: SnakeContext,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SnakeDeath {
+    pub snake: usize,
+    pub cause: DeathCause,
+    pub eliminated: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MultiTickResult {
+    pub deaths: Vec<SnakeDeath>,
+    pub game_over: Option<GameOverReason>,
+}
+
+// Several snakes on one board, sharing its apples and level walls. Rule sets,
+// observers and game modes apply to every player; fog of war, creatures and
+// the shrinking arena stay single-player features.
+#[derive(Debug)]
+pub struct MultiSnakeContext {
+    config: GameConfig,
+    rules: Arc<dyn RuleSet>,
+    players: Vec<PlayerSnake>,
+    apples: BTreeSet<Point>,
+    walls: BTreeSet<Point>,
+    tick: u64,
+    seed: u64,
+}
+
+impl PlayerSnake {
+    pub fn head(&self) -> Option<Point> {
+        self.game.snake.front().copied()
+    }
+
+    pub fn segments(&self) -> impl Iterator<Item = Point> + '_ {
+        self.game.snake_segments()
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.game.current_direction()
+    }
+
+    pub fn score(&self) -> u32 {
+        self.game.score()
+    }
+
+    pub fn lives_left(&self) -> u32 {
+        self.game.lives_left()
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        self.game.is_invulnerable()
+    }
+
+    // An eliminated snake is off the board until the next game.
+    pub fn is_eliminated(&self) -> bool {
+        self.game.lives_left() == 0
+    }
+}
+
+impl MultiTickResult {
+    pub fn is_eliminated(&self, snake: usize) -> bool {
+        self.deaths
+            .iter()
+            .any(|death| death.snake == snake && death.eliminated)
+    }
+}
+
+impl MultiSnakeContext {
+    #[cfg(feature = "std")]
+    pub fn new(config: GameConfig, players: usize) -> Self {
+        Self::with_rule_set(config, Arc::new(crate::rule_set::DefaultRuleSet), players)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn with_rule_set(config: GameConfig, rules: Arc<dyn RuleSet>, players: usize) -> Self {
+        Self::with_rng(config, rules, players, &mut rand::thread_rng())
+    }
+
+    pub fn with_rng(
+        config: GameConfig,
+        rules: Arc<dyn RuleSet>,
+        players: usize,
+        rng: &mut dyn RngCore,
+    ) -> Self {
+        let seed = config.seed.unwrap_or_else(|| rng.next_u64());
+        let mut context = Self {
+            config: GameConfig {
+                seed: Some(seed),
+                ..config
+            },
+            rules,
+            players: Vec::with_capacity(players),
+            apples: BTreeSet::new(),
+            walls: BTreeSet::new(),
+            tick: 0,
+            seed,
+        };
+        for index in 0..players {
+            let mut game =
+                SnakeContext::with_rng(context.config.clone(), Arc::clone(&context.rules), rng);
+            game.generate_snake(context.initial_head_position(index, players));
+            game.lives_left = game.lives_left.max(1);
+            context.players.push(PlayerSnake { game });
+        }
+        context.place_level();
+        context
+    }
+
+    // Players start on evenly spaced rows, all heading left.
+    fn initial_head_position(&self, index: usize, players: usize) -> Point {
+        let BoardConfig { width, height, .. } = self.config.board;
+        let row = (index as u32 + 1) * height / (players as u32 + 1);
+        Point(width / 2 - 1, row.min(height - 1))
+    }
+
+    fn place_level(&mut self) {
+        let level = match &self.config.level {
+            Some(level) => level,
+            None => return,
+        };
+        let board = &self.config.board;
+        let players = &self.players;
+        self.walls.extend(
+            level
+                .walls
+                .iter()
+                .filter(|&&wall| {
+                    board.contains(wall)
+                        && !players
+                            .iter()
+                            .any(|player| player.game.snake.contains(&wall))
+                })
+                .cloned(),
+        );
+    }
+
+    pub fn register_observer(&mut self, index: usize, observer: Box<dyn GameObserver>) {
+        self.players[index].game.register_observer(observer);
+    }
+
+    pub fn snakes(&self) -> &[PlayerSnake] {
+        &self.players
+    }
+
+    pub fn snake(&self, index: usize) -> &PlayerSnake {
+        &self.players[index]
+    }
+
+    pub fn snake_at(&self, point: Point) -> Option<usize> {
+        self.players
+            .iter()
+            .position(|player| player.game.snake.contains(&point))
+    }
+
+    pub fn apples(&self) -> impl Iterator<Item = Point> + '_ {
+        self.apples.iter().cloned()
+    }
+
+    pub fn walls(&self) -> impl Iterator<Item = Point> + '_ {
+        self.walls.iter().cloned()
+    }
+
+    pub fn is_apple(&self, point: Point) -> bool {
+        self.apples.contains(&point)
+    }
+
+    pub fn is_wall(&self, point: Point) -> bool {
+        self.walls.contains(&point)
+    }
+
+    pub fn plane_width(&self) -> u32 {
+        self.config.board.width
+    }
+
+    pub fn plane_height(&self) -> u32 {
+        self.config.board.height
+    }
+
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    // The pace follows the longest snake and the best score on the board.
+    pub fn tick_interval_milliseconds(&self) -> u64 {
+        let length = self
+            .players
+            .iter()
+            .map(|player| player.game.snake.len())
+            .max();
+        let score = self.players.iter().map(PlayerSnake::score).max();
+        self.config
+            .speed_policy
+            .interval_milliseconds(length.unwrap_or(0), score.unwrap_or(0))
+    }
+
+    pub fn change_direction(
+        &mut self,
+        index: usize,
+        direction: Direction,
+    ) -> Result<(), DirectionChangeError> {
+        let player = &mut self.players[index];
+        if player.is_eliminated() {
+            return Err(DirectionChangeError);
+        }
+        player.game.change_direction(direction)
+    }
+
+    // All snakes move at once. A head entering a wall, its own body or any part
+    // of another snake as it is after the move dies unless the rule set ignores
+    // the collision, so meeting head-on kills both snakes.
+    pub fn update_positions(&mut self) -> MultiTickResult {
+        self.tick += 1;
+        for player in &mut self.players {
+            player.game.tick = self.tick;
+            player.game.apples = self.apples.clone();
+            player.game.walls = self.walls.clone();
+            if !player.is_eliminated() {
+                player.game.stats.ticks += 1;
+            }
+        }
+        let steps = self
+            .players
+            .iter()
+            .map(|player| {
+                if player.is_eliminated() {
+                    Step::Stay
+                } else {
+                    player.game.next_step()
+                }
+            })
+            .collect::<Vec<Step>>();
+        let moved = self
+            .players
+            .iter()
+            .zip(&steps)
+            .map(|(player, step)| {
+                let mut body = player.game.snake_segments().collect::<VecDeque<Point>>();
+                if let Step::Move(new_head) = *step {
+                    if !self.apples.contains(&new_head) {
+                        body.pop_back();
+                    }
+                    body.push_front(new_head);
+                }
+                body
+            })
+            .collect::<Vec<VecDeque<Point>>>();
+
+        let mut deaths = Vec::new();
+        let mut game_over = None;
+        for (index, step) in steps.into_iter().enumerate() {
+            let new_head = match step {
+                Step::Move(new_head) => new_head,
+                Step::Stay => continue,
+                Step::Die(cause) => {
+                    deaths.push((index, cause));
+                    continue;
+                }
+            };
+            let hits_other_snake = moved
+                .iter()
+                .enumerate()
+                .any(|(other, body)| other != index && body.contains(&new_head));
+            let game = &mut self.players[index].game;
+            let tail = game.snake.pop_back();
+            let cause = game
+                .detect_collision(new_head)
+                .or_else(|| hits_other_snake.then_some(DeathCause::SnakeCollision));
+            if let Some(cause) = game.fatal_collision(cause) {
+                game.snake.extend(tail);
+                deaths.push((index, cause));
+                continue;
+            }
+            game.move_head(new_head);
+            game.notify(GameEvent::SnakeMoved {
+                head: new_head,
+                length: game.snake.len(),
+            });
+            self.apples.retain(|apple| game.apples.contains(apple));
+            game_over = game_over.or_else(|| self.rules.after_move(game));
+        }
+
+        let deaths = deaths
+            .into_iter()
+            .map(|(index, cause)| SnakeDeath {
+                snake: index,
+                cause,
+                eliminated: !self.lose_life(index, cause),
+            })
+            .collect();
+        self.spawn_apple();
+        self.insert_hazard();
+        let game_over = game_over.or_else(|| {
+            self.players
+                .iter()
+                .filter(|player| !player.is_eliminated())
+                .find_map(|player| {
+                    let game = &player.game;
+                    game.config
+                        .mode
+                        .end_condition(&game.stats, game.snake.len())
+                })
+        });
+        MultiTickResult { deaths, game_over }
+    }
+
+    // Returns whether the snake is back on the board.
+    fn lose_life(&mut self, index: usize, cause: DeathCause) -> bool {
+        let game = &mut self.players[index].game;
+        game.stats.deaths += 1;
+        game.lives_left = game.lives_left.saturating_sub(1);
+        let result = game.game_result(GameOverReason::Died(cause));
+        game.snake.clear();
+        if game.lives_left > 0 {
+            let start = self.initial_head_position(index, self.players.len());
+            let head = self.players[index]
+                .game
+                .find_spawn_position_from(start, |point| self.snake_at(point).is_none());
+            if let Some(head) = head {
+                self.players[index].game.respawn_snake(head);
+                return true;
+            }
+        }
+
+        let game = &mut self.players[index].game;
+        game.lives_left = 0;
+        game.last_game_result = Some(result);
+        game.notify(GameEvent::GameOver(result));
+        false
+    }
+
+    fn is_free(&self, point: Point) -> bool {
+        !self.apples.contains(&point)
+            && !self.walls.contains(&point)
+            && self.snake_at(point).is_none()
+    }
+
+    fn spawn_apple(&mut self) {
+        let game = match self.players.first() {
+            Some(player) => &player.game,
+            None => return,
+        };
+        let mut rng = tick_rng(self.seed, self.tick, APPLE_RNG_STREAM);
+        let apple = match self.rules.on_spawn(game, &mut rng) {
+            Some(apple) if self.config.board.contains(apple) && self.is_free(apple) => apple,
+            _ => return,
+        };
+        self.apples.insert(apple);
+        for player in &mut self.players {
+            player.game.notify(GameEvent::AppleSpawned(apple));
+        }
+    }
+
+    // Hazards keep clear of every head, as they do of a single snake's.
+    fn insert_hazard(&mut self) {
+        let stats = GameStats {
+            ticks: self.tick,
+            ..GameStats::default()
+        };
+        if !self.config.mode.is_hazard_due(&stats) {
+            return;
+        }
+        let mut alive = self
+            .players
+            .iter()
+            .filter(|player| !player.is_eliminated())
+            .map(|player| player.game.free_fields_away_from_head());
+        let mut free_fields = match alive.next() {
+            Some(free_fields) => free_fields,
+            None => return,
+        };
+        for fields in alive {
+            free_fields.retain(|field| fields.contains(field));
+        }
+        free_fields.retain(|&field| self.is_free(field));
+        let mut rng = tick_rng(self.seed, self.tick, HAZARD_RNG_STREAM);
+        if let Some(&hazard) = free_fields.choose(&mut rng) {
+            self.walls.insert(hazard);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use alloc::boxed::Box;
+    use alloc::collections::LinkedList;
+
+    use crate::game_config::GameConfig;
+    use crate::game_mode::{DeathCause, GameMode, GameOverReason};
+    use crate::game_observer::{GameEvent, GameObserver};
+    use crate::{Direction, Point};
+
+    use super::{MultiSnakeContext, SnakeDeath};
+
+    fn two_player_game(lives: u32) -> MultiSnakeContext {
+        MultiSnakeContext::new(
+            GameConfig {
+                lives,
+                apple_spawn_one_in: 0,
+                seed: Some(7),
+                ..GameConfig::default()
+            },
+            2,
+        )
+    }
+
+    fn place(game: &mut MultiSnakeContext, index: usize, body: &[Point], direction: Direction) {
+        game.players[index].game.snake = body.iter().cloned().collect::<LinkedList<Point>>();
+        game.players[index].game.direction = direction;
+    }
+
+    #[derive(Debug)]
+    struct RecordingObserver(Arc<Mutex<Vec<GameEvent>>>);
+
+    impl GameObserver for RecordingObserver {
+        fn on_event(&mut self, event: &GameEvent) {
+            self.0.lock().unwrap().push(*event);
+        }
+    }
+
+    #[test]
+    fn test_should_place_players_on_separate_rows_of_one_board() {
+        let game = two_player_game(1);
+        assert_eq!(Some(Point(9, 3)), game.snake(0).head());
+        assert_eq!(Some(Point(9, 6)), game.snake(1).head());
+        assert_eq!(Some(1), game.snake_at(Point(10, 6)));
+        assert_eq!(None, game.snake_at(Point(0, 0)));
+    }
+
+    #[test]
+    fn test_should_kill_only_the_snake_running_into_another_body() {
+        let mut game = two_player_game(1);
+        place(
+            &mut game,
+            0,
+            &[Point(5, 4), Point(6, 4), Point(7, 4)],
+            Direction::Left,
+        );
+        place(
+            &mut game,
+            1,
+            &[Point(6, 3), Point(6, 2), Point(6, 1)],
+            Direction::Down,
+        );
+        let result = game.update_positions();
+        assert_eq!(
+            vec![SnakeDeath {
+                snake: 1,
+                cause: DeathCause::SnakeCollision,
+                eliminated: true,
+            }],
+            result.deaths
+        );
+        assert!(result.is_eliminated(1));
+        assert!(game.snake(1).is_eliminated());
+        assert_eq!(None, game.snake_at(Point(6, 3)));
+        assert_eq!(Some(Point(4, 4)), game.snake(0).head());
+    }
+
+    #[test]
+    fn test_should_kill_both_snakes_when_meeting_head_on() {
+        let mut game = two_player_game(2);
+        place(
+            &mut game,
+            0,
+            &[Point(5, 3), Point(4, 3), Point(3, 3)],
+            Direction::Right,
+        );
+        place(
+            &mut game,
+            1,
+            &[Point(7, 3), Point(8, 3), Point(9, 3)],
+            Direction::Left,
+        );
+        let result = game.update_positions();
+        assert_eq!(2, result.deaths.len());
+        assert!(result
+            .deaths
+            .iter()
+            .all(|death| death.cause == DeathCause::SnakeCollision && !death.eliminated));
+        assert_eq!(1, game.snake(0).lives_left());
+        assert_eq!(3, game.snake(1).segments().count());
+        assert_ne!(game.snake(0).head(), game.snake(1).head());
+    }
+
+    #[test]
+    fn test_should_grow_and_score_only_the_snake_eating_the_apple() {
+        let mut game = two_player_game(1);
+        game.apples.insert(Point(8, 3));
+        assert_eq!(
+            Err(crate::DirectionChangeError),
+            game.change_direction(0, Direction::Right)
+        );
+        game.update_positions();
+        assert_eq!(1, game.snake(0).score());
+        assert_eq!(4, game.snake(0).segments().count());
+        assert_eq!(0, game.snake(1).score());
+        assert_eq!(3, game.snake(1).segments().count());
+        assert!(!game.is_apple(Point(8, 3)));
+    }
+
+    #[test]
+    fn test_should_ignore_own_body_while_respawned_snake_is_invulnerable() {
+        let mut game = two_player_game(2);
+        place(
+            &mut game,
+            0,
+            &[Point(5, 4), Point(6, 4), Point(7, 4)],
+            Direction::Left,
+        );
+        place(
+            &mut game,
+            1,
+            &[Point(6, 3), Point(6, 2), Point(6, 1)],
+            Direction::Down,
+        );
+        let result = game.update_positions();
+        assert!(!result.is_eliminated(1));
+        assert!(game.snake(1).is_invulnerable());
+
+        place(
+            &mut game,
+            1,
+            &[
+                Point(15, 1),
+                Point(15, 2),
+                Point(14, 2),
+                Point(14, 1),
+                Point(14, 0),
+            ],
+            Direction::Down,
+        );
+        let result = game.update_positions();
+        assert!(result.deaths.is_empty());
+        assert_eq!(Some(Point(15, 2)), game.snake(1).head());
+    }
+
+    #[test]
+    fn test_should_end_game_by_mode_and_report_moves_to_observers() {
+        let mut game = MultiSnakeContext::new(
+            GameConfig {
+                mode: GameMode::TimeLimited { tick_budget: 2 },
+                apple_spawn_one_in: 0,
+                seed: Some(7),
+                ..GameConfig::default()
+            },
+            2,
+        );
+        let events = Arc::new(Mutex::new(Vec::new()));
+        game.register_observer(1, Box::new(RecordingObserver(Arc::clone(&events))));
+        assert_eq!(None, game.update_positions().game_over);
+        assert_eq!(
+            Some(GameOverReason::TimeUp),
+            game.update_positions().game_over
+        );
+        assert_eq!(
+            vec![
+                GameEvent::SnakeMoved {
+                    head: Point(8, 6),
+                    length: 3,
+                },
+                GameEvent::SnakeMoved {
+                    head: Point(7, 6),
+                    length: 3,
+                },
+            ],
+            *events.lock().unwrap()
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use rand::distributions::Uniform;
+use rand::prelude::Distribution;
+use rand::RngCore;
+
+use core::fmt::Debug;
+
+use crate::game_config::board::BoardConfig;
+use crate::game_mode::{DeathCause, GameOverReason};
+use crate::{Direction, Point, SnakeContext};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CollisionOutcome {
+    Die,
+    Ignore,
+}
+
+/// Hooks called by `SnakeContext` at each step of a tick.
+///
+/// Every hook defaults to the built-in behavior, so a variant only overrides
+/// the hooks it changes.
+pub trait RuleSet: Debug + Send + Sync {
+    /// Returns the next head position, or `None` to veto the move.
+    fn before_move(&self, game: &SnakeContext, head: Point, direction: Direction) -> Option<Point> {
+        game.config().board.step(head, direction)
+    }
+
+    /// Called once the head has moved. Returning a reason ends the game.
+    fn after_move(&self, _game: &SnakeContext) -> Option<GameOverReason> {
+        None
+    }
+
+    /// Returns the number of segments to grow by, or `None` to leave the apple uneaten.
+    fn on_eat(&self, _game: &SnakeContext, _apple: Point) -> Option<u32> {
+        Some(1)
+    }
+
+    fn on_collision(&self, game: &SnakeContext, cause: DeathCause) -> CollisionOutcome {
+        match cause {
+            DeathCause::SelfCollision | DeathCause::CreatureContact if game.is_invulnerable() => {
+                CollisionOutcome::Ignore
+            }
+            _ => CollisionOutcome::Die,
+        }
+    }
+
+    /// Returns the position of an apple to spawn this tick, if any.
+    fn on_spawn(&self, game: &SnakeContext, rng: &mut dyn RngCore) -> Option<Point> {
+        random_apple_position(rng, &game.config().board, game.config().apple_spawn_one_in)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultRuleSet;
+
+impl RuleSet for DefaultRuleSet {}
+
+pub fn wrap_around_step(head: Point, direction: Direction, width: u32, height: u32) -> Point {
+    let Point(head_x, head_y) = head;
+    match direction {
+        Direction::Up if head_y == 0 => Point(head_x, height - 1),
+        Direction::Up => Point(head_x, head_y - 1),
+        Direction::Down if head_y >= height - 1 => Point(head_x, 0),
+        Direction::Down => Point(head_x, head_y + 1),
+        Direction::Left if head_x == 0 => Point(width - 1, head_y),
+        Direction::Left => Point(head_x - 1, head_y),
+        Direction::Right if head_x >= width - 1 => Point(0, head_y),
+        Direction::Right => Point(head_x + 1, head_y),
+    }
+}
+
+pub fn random_apple_position(
+    rng: &mut dyn RngCore,
+    board: &BoardConfig,
+    one_in: u32,
+) -> Option<Point> {
+    if one_in == 0 {
+        return None;
+    }
+    let random_x = Uniform::from(0..board.width - 1);
+    let random_y = Uniform::from(0..board.height - 1);
+
+    let random_apple = Uniform::from(0..one_in);
+
+    if random_apple.sample(rng) == 0 {
+        Some(Point(random_x.sample(rng), random_y.sample(rng)))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Direction, Point};
+
+    use super::wrap_around_step;
+
+    #[test]
+    fn test_should_wrap_to_opposite_wall_when_stepping_out_of_the_plane() {
+        let step = |head, direction| wrap_around_step(head, direction, 20, 10);
+        assert_eq!(Point(19, 3), step(Point(0, 3), Direction::Left));
+        assert_eq!(Point(0, 3), step(Point(19, 3), Direction::Right));
+        assert_eq!(Point(4, 9), step(Point(4, 0), Direction::Up));
+        assert_eq!(Point(4, 0), step(Point(4, 9), Direction::Down));
+        assert_eq!(Point(5, 3), step(Point(4, 3), Direction::Right));
+    }
+}
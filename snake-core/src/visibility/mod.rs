@@ -0,0 +1,67 @@
+use crate::game_config::board::BoardConfig;
+use crate::{PlaneField, Point};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DistanceMetric {
+    #[default]
+    Manhattan,
+    Euclidean,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VisibilityConfig {
+    pub radius: u32,
+    pub metric: DistanceMetric,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VisibleCell {
+    Visible(PlaneField),
+    Remembered(PlaneField),
+    Hidden,
+}
+
+impl VisibilityConfig {
+    // Distances are measured the same way the snake moves, across the edges of
+    // a wrapping board.
+    pub fn reveals(&self, head: Point, point: Point, board: &BoardConfig) -> bool {
+        let (dx, dy) = board.axis_distances(head, point);
+        let (dx, dy) = (dx as u64, dy as u64);
+        let radius = self.radius as u64;
+        match self.metric {
+            DistanceMetric::Manhattan => dx + dy <= radius,
+            DistanceMetric::Euclidean => dx * dx + dy * dy <= radius * radius,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_config::board::{BoardConfig, Topology};
+    use crate::Point;
+
+    use super::{DistanceMetric, VisibilityConfig};
+
+    #[test]
+    fn test_should_reveal_cells_by_metric_and_across_edges() {
+        let manhattan = VisibilityConfig {
+            radius: 2,
+            metric: DistanceMetric::Manhattan,
+        };
+        let euclidean = VisibilityConfig {
+            radius: 2,
+            metric: DistanceMetric::Euclidean,
+        };
+        let board = BoardConfig::default();
+        assert!(!manhattan.reveals(Point(5, 5), Point(6, 7), &board));
+        assert!(euclidean.reveals(Point(5, 5), Point(6, 6), &board));
+        assert!(!euclidean.reveals(Point(5, 5), Point(7, 7), &board));
+        assert!(manhattan.reveals(Point(0, 0), Point(19, 9), &board));
+
+        let walled = BoardConfig {
+            topology: Topology::Walled,
+            ..board
+        };
+        assert!(!manhattan.reveals(Point(0, 0), Point(19, 9), &walled));
+    }
+}
@@ -0,0 +1,59 @@
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "snake_core.h"
+
+#define CHECK(condition)                                                   \
+    do {                                                                   \
+        if (!(condition)) {                                                \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__,        \
+                    __LINE__, #condition);                                 \
+            return 1;                                                      \
+        }                                                                  \
+    } while (0)
+
+int main(void) {
+    SnakeGameConfig config;
+    CHECK(snake_game_config_default(&config) == SNAKE_STATUS_OK);
+    config.use_seed = 1;
+    config.seed = 7;
+
+    SnakeContext *game = NULL;
+    CHECK(snake_game_new(&config, &game) == SNAKE_STATUS_OK);
+    CHECK(game != NULL);
+
+    uint32_t width = 0, height = 0;
+    CHECK(snake_game_dimensions(game, &width, &height) == SNAKE_STATUS_OK);
+    CHECK(width == 20 && height == 10);
+
+    uint32_t cell = SNAKE_CELL_EMPTY;
+    CHECK(snake_game_cell(game, 10, 5, &cell) == SNAKE_STATUS_OK);
+    CHECK(cell == SNAKE_CELL_BODY);
+    CHECK(snake_game_cell(game, width, 0, &cell) == SNAKE_STATUS_OUT_OF_BOUNDS);
+
+    CHECK(snake_game_change_direction(game, SNAKE_DIRECTION_RIGHT) ==
+          SNAKE_STATUS_DIRECTION_REJECTED);
+    CHECK(snake_game_change_direction(game, SNAKE_DIRECTION_UP) == SNAKE_STATUS_OK);
+
+    uint8_t game_over = 1;
+    CHECK(snake_game_tick(game, &game_over) == SNAKE_STATUS_OK);
+    CHECK(game_over == 0);
+    CHECK(snake_game_cell(game, 10, 4, &cell) == SNAKE_STATUS_OK);
+    CHECK(cell == SNAKE_CELL_BODY);
+
+    size_t length = 0;
+    CHECK(snake_game_snapshot(game, NULL, 0, &length) == SNAKE_STATUS_BUFFER_TOO_SMALL);
+    CHECK(length > 0);
+    uint8_t *buffer = malloc(length);
+    CHECK(buffer != NULL);
+    CHECK(snake_game_snapshot(game, buffer, length, &length) == SNAKE_STATUS_OK);
+    CHECK(buffer[0] == 'S' && buffer[1] == 'N');
+    free(buffer);
+
+    CHECK(snake_game_tick(NULL, NULL) == SNAKE_STATUS_NULL_POINTER);
+    snake_game_free(game);
+    snake_game_free(NULL);
+
+    puts("ffi ok");
+    return 0;
+}
@@ -0,0 +1,117 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn manifest_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn exported_functions(source: &str) -> Vec<&str> {
+    source
+        .split("extern \"C\" fn ")
+        .skip(1)
+        .filter_map(|rest| rest.split('(').next())
+        .collect()
+}
+
+fn build_shared_library(target_dir: &Path) {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .current_dir(manifest_dir())
+        .args([
+            "rustc",
+            "--lib",
+            "--features",
+            "ffi",
+            "--crate-type",
+            "cdylib",
+        ])
+        .arg("--target-dir")
+        .arg(target_dir)
+        .output()
+        .expect("cargo should start");
+    assert!(
+        output.status.success(),
+        "building the ffi library failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_should_declare_every_exported_function_in_header() {
+    let source = fs::read_to_string(manifest_dir().join("src/ffi/mod.rs")).unwrap();
+    let header = fs::read_to_string(manifest_dir().join("include/snake_core.h")).unwrap();
+
+    let functions = exported_functions(&source);
+    assert!(!functions.is_empty());
+    for function in functions {
+        assert!(
+            header.contains(&format!(" {}(", function)),
+            "{} is missing from include/snake_core.h",
+            function
+        );
+    }
+}
+
+#[test]
+fn test_should_match_header_generated_from_ffi_module() {
+    let header_path = manifest_dir().join("include/snake_core.h");
+    let config = cbindgen::Config::from_file(manifest_dir().join("cbindgen.toml"))
+        .expect("cbindgen.toml should parse");
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(manifest_dir().join("src/ffi/mod.rs"))
+        .generate()
+        .expect("header should generate")
+        .write(&mut generated);
+    let generated = String::from_utf8(generated).unwrap();
+
+    if env::var_os("SNAKE_CORE_UPDATE_HEADER").is_some() {
+        fs::write(&header_path, &generated).unwrap();
+    }
+    assert!(
+        fs::read_to_string(&header_path).unwrap() == generated,
+        "include/snake_core.h is out of date, regenerate it with \
+         `SNAKE_CORE_UPDATE_HEADER=1 cargo test --test ffi_c_program`"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_should_run_c_program_against_ffi_library() {
+    let target_dir = manifest_dir().join("target").join("ffi-check");
+    build_shared_library(&target_dir);
+
+    let library_dir = target_dir.join("debug");
+    let program = library_dir.join("snake_ffi_test");
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let compiled = Command::new(compiler)
+        .arg(manifest_dir().join("tests/ffi/snake_ffi_test.c"))
+        .arg("-I")
+        .arg(manifest_dir().join("include"))
+        .arg("-L")
+        .arg(&library_dir)
+        .args(["-lsnake_core", "-o"])
+        .arg(&program)
+        .output()
+        .expect("C compiler should start");
+    assert!(
+        compiled.status.success(),
+        "compiling the C program failed:\n{}",
+        String::from_utf8_lossy(&compiled.stderr)
+    );
+
+    let run = Command::new(&program)
+        .env("LD_LIBRARY_PATH", &library_dir)
+        .env("DYLD_LIBRARY_PATH", &library_dir)
+        .output()
+        .expect("C program should start");
+    assert!(
+        run.status.success(),
+        "C program failed:\n{}{}",
+        String::from_utf8_lossy(&run.stdout),
+        String::from_utf8_lossy(&run.stderr)
+    );
+}
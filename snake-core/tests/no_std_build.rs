@@ -0,0 +1,56 @@
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+// A bare-metal target has no `std` at all, so any std use in the crate or its
+// dependencies fails the build instead of silently linking the host's std.
+const NO_STD_TARGET: &str = "thumbv7em-none-eabihf";
+// Set to 1 in CI so a missing target fails the test instead of skipping it.
+const REQUIRE_NO_STD_VAR: &str = "SNAKE_CORE_REQUIRE_NO_STD";
+
+fn is_target_installed(target: &str) -> bool {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let sysroot = match Command::new(rustc).args(["--print", "sysroot"]).output() {
+        Ok(output) if output.status.success() => output.stdout,
+        _ => return false,
+    };
+    PathBuf::from(String::from_utf8_lossy(&sysroot).trim())
+        .join("lib")
+        .join("rustlib")
+        .join(target)
+        .exists()
+}
+
+#[test]
+fn test_should_build_without_std_when_default_features_disabled() {
+    if !is_target_installed(NO_STD_TARGET) {
+        assert!(
+            env::var(REQUIRE_NO_STD_VAR).as_deref() != Ok("1"),
+            "{} is set but the {} target is not installed",
+            REQUIRE_NO_STD_VAR,
+            NO_STD_TARGET
+        );
+        eprintln!(
+            "skipping: install the {} target to check the no_std build",
+            NO_STD_TARGET
+        );
+        return;
+    }
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+
+    let output = Command::new(cargo)
+        .current_dir(&manifest_dir)
+        .args(["check", "--lib", "--no-default-features", "--target"])
+        .arg(NO_STD_TARGET)
+        .arg("--target-dir")
+        .arg(manifest_dir.join("target").join("no-std-check"))
+        .output()
+        .expect("cargo should start");
+
+    assert!(
+        output.status.success(),
+        "no_std build failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,141 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use snake_core::{Direction, SnakeContext};
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
+pub trait Controller {
+    fn next_direction(&mut self, game: &SnakeContext) -> Option<Direction>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControllerKind {
+    Straight,
+    Random,
+    AppleChaser,
+}
+
+impl ControllerKind {
+    pub fn create(&self, seed: u64) -> Box<dyn Controller + Send> {
+        match self {
+            ControllerKind::Straight => Box::new(StraightController),
+            ControllerKind::Random => Box::new(RandomController {
+                rng: StdRng::seed_from_u64(seed),
+            }),
+            ControllerKind::AppleChaser => Box::new(AppleChaserController),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UnknownControllerError;
+
+impl TryFrom<&str> for ControllerKind {
+    type Error = UnknownControllerError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "straight" => Ok(ControllerKind::Straight),
+            "random" => Ok(ControllerKind::Random),
+            "apple-chaser" => Ok(ControllerKind::AppleChaser),
+            _ => Err(UnknownControllerError),
+        }
+    }
+}
+
+pub struct StraightController;
+
+impl Controller for StraightController {
+    fn next_direction(&mut self, _: &SnakeContext) -> Option<Direction> {
+        None
+    }
+}
+
+pub struct RandomController {
+    rng: StdRng,
+}
+
+impl Controller for RandomController {
+    fn next_direction(&mut self, game: &SnakeContext) -> Option<Direction> {
+        safe_directions(game).choose(&mut self.rng).cloned()
+    }
+}
+
+pub struct AppleChaserController;
+
+impl Controller for AppleChaserController {
+    fn next_direction(&mut self, game: &SnakeContext) -> Option<Direction> {
+        let head = game.head();
+        let nearest_apple = game
+            .apples()
+            .min_by_key(|&apple| game.config().board.distance(head, apple));
+
+        let safe_directions = safe_directions(game);
+        match nearest_apple {
+            Some(apple) => safe_directions.into_iter().min_by_key(|&direction| {
+                let next = game.config().board.step(head, direction);
+                next.map(|next| game.config().board.distance(next, apple))
+            }),
+            None if safe_directions.contains(&game.current_direction()) => None,
+            None => safe_directions.first().cloned(),
+        }
+    }
+}
+
+pub fn opposite_direction(direction: Direction) -> Direction {
+    match direction {
+        Direction::Up => Direction::Down,
+        Direction::Down => Direction::Up,
+        Direction::Left => Direction::Right,
+        Direction::Right => Direction::Left,
+    }
+}
+
+fn safe_directions(game: &SnakeContext) -> Vec<Direction> {
+    let head = game.head();
+    let reverse = opposite_direction(game.current_direction());
+    DIRECTIONS
+        .into_iter()
+        .filter(|&direction| direction != reverse)
+        .filter(
+            |&direction| match game.config().board.step(head, direction) {
+                Some(next) => {
+                    !game.snake_segments().any(|segment| segment == next)
+                        && !game.walls().any(|wall| wall == next)
+                }
+                None => false,
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use snake_core::{Direction, SnakeContext};
+
+    use super::{AppleChaserController, Controller, ControllerKind};
+
+    #[test]
+    fn test_should_never_choose_reverse_direction_when_random_controller() {
+        let mut game = SnakeContext::new();
+        game.new_game();
+        let mut controller = ControllerKind::Random.create(3);
+        for _ in 0..50 {
+            assert_ne!(Some(Direction::Right), controller.next_direction(&game));
+        }
+    }
+
+    #[test]
+    fn test_should_keep_direction_when_apple_chaser_sees_no_apples() {
+        let mut game = SnakeContext::new();
+        game.new_game();
+        assert_eq!(None, AppleChaserController.next_direction(&game));
+    }
+}
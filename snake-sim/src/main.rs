@@ -0,0 +1,49 @@
+mod controller;
+mod report;
+mod sim_options;
+mod simulation;
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::process::exit;
+
+use crate::{
+    report::{write_csv, write_json, write_summary_csv},
+    sim_options::{ReportFormat, SimOptions, USAGE},
+    simulation::run_games,
+};
+
+fn main() -> io::Result<()> {
+    if std::env::args().any(|arg| arg == "--help") {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+
+    let options = match SimOptions::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}\n{}", error, USAGE);
+            exit(2);
+        }
+    };
+
+    let outcomes = run_games(
+        &options.game_config,
+        options.controller,
+        options.base_seed,
+        options.games,
+        options.max_ticks,
+        options.threads,
+    );
+
+    let mut writer: BufWriter<Box<dyn Write>> = match &options.output {
+        Some(path) => BufWriter::new(Box::new(File::create(path)?)),
+        None => BufWriter::new(Box::new(io::stdout())),
+    };
+    match options.format {
+        ReportFormat::Csv => write_csv(&mut writer, &outcomes)?,
+        ReportFormat::SummaryCsv => write_summary_csv(&mut writer, &outcomes)?,
+        ReportFormat::Json => write_json(&mut writer, &outcomes)?,
+    }
+    writer.flush()
+}
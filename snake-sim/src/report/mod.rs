@@ -0,0 +1,228 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::simulation::GameOutcome;
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Distribution {
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub p10: u64,
+    pub p25: u64,
+    pub median: u64,
+    pub p75: u64,
+    pub p90: u64,
+    pub p99: u64,
+    pub histogram: BTreeMap<u64, usize>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct SimulationSummary {
+    pub games: usize,
+    pub final_length: Option<Distribution>,
+    pub ticks_survived: Option<Distribution>,
+    pub score: Option<Distribution>,
+    pub end_reasons: BTreeMap<&'static str, usize>,
+}
+
+impl Distribution {
+    pub fn from_values(values: impl Iterator<Item = u64>) -> Option<Self> {
+        let mut values = values.collect::<Vec<u64>>();
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_unstable();
+
+        let mut histogram = BTreeMap::new();
+        for &value in &values {
+            *histogram.entry(value).or_insert(0) += 1;
+        }
+        Some(Self {
+            min: values[0],
+            max: values[values.len() - 1],
+            mean: values.iter().sum::<u64>() as f64 / values.len() as f64,
+            p10: percentile(&values, 10),
+            p25: percentile(&values, 25),
+            median: percentile(&values, 50),
+            p75: percentile(&values, 75),
+            p90: percentile(&values, 90),
+            p99: percentile(&values, 99),
+            histogram,
+        })
+    }
+}
+
+fn percentile(sorted_values: &[u64], percent: usize) -> u64 {
+    let rank = (sorted_values.len() * percent).div_ceil(100);
+    sorted_values[rank.saturating_sub(1)]
+}
+
+impl SimulationSummary {
+    pub fn from_outcomes(outcomes: &[GameOutcome]) -> Self {
+        let mut end_reasons = BTreeMap::new();
+        for outcome in outcomes {
+            *end_reasons.entry(outcome.end.label()).or_insert(0) += 1;
+        }
+        Self {
+            games: outcomes.len(),
+            final_length: Distribution::from_values(
+                outcomes.iter().map(|outcome| outcome.final_length as u64),
+            ),
+            ticks_survived: Distribution::from_values(outcomes.iter().map(|outcome| outcome.ticks)),
+            score: Distribution::from_values(outcomes.iter().map(|outcome| outcome.score as u64)),
+            end_reasons,
+        }
+    }
+}
+
+pub fn write_csv<W: Write>(writer: &mut W, outcomes: &[GameOutcome]) -> io::Result<()> {
+    writeln!(writer, "game,seed,final_length,ticks_survived,score,end_reason")?;
+    for (index, outcome) in outcomes.iter().enumerate() {
+        writeln!(
+            writer,
+            "{},{},{},{},{},{}",
+            index,
+            outcome.seed,
+            outcome.final_length,
+            outcome.ticks,
+            outcome.score,
+            outcome.end.label()
+        )?;
+    }
+    Ok(())
+}
+
+// One `metric,kind,key,value` row per statistic, histogram bucket and end reason.
+pub fn write_summary_csv<W: Write>(writer: &mut W, outcomes: &[GameOutcome]) -> io::Result<()> {
+    let summary = SimulationSummary::from_outcomes(outcomes);
+    writeln!(writer, "metric,kind,key,value")?;
+    writeln!(writer, "games,count,all,{}", summary.games)?;
+    let distributions = [
+        ("final_length", &summary.final_length),
+        ("ticks_survived", &summary.ticks_survived),
+        ("score", &summary.score),
+    ];
+    for (metric, distribution) in distributions {
+        if let Some(distribution) = distribution {
+            write_distribution_rows(writer, metric, distribution)?;
+        }
+    }
+    for (reason, count) in &summary.end_reasons {
+        writeln!(writer, "end_reason,count,{},{}", reason, count)?;
+    }
+    Ok(())
+}
+
+fn write_distribution_rows<W: Write>(
+    writer: &mut W,
+    metric: &str,
+    distribution: &Distribution,
+) -> io::Result<()> {
+    let statistics = [
+        ("min", distribution.min),
+        ("p10", distribution.p10),
+        ("p25", distribution.p25),
+        ("median", distribution.median),
+        ("p75", distribution.p75),
+        ("p90", distribution.p90),
+        ("p99", distribution.p99),
+        ("max", distribution.max),
+    ];
+    writeln!(writer, "{},stat,mean,{}", metric, distribution.mean)?;
+    for (name, value) in statistics {
+        writeln!(writer, "{},stat,{},{}", metric, name, value)?;
+    }
+    for (value, count) in &distribution.histogram {
+        writeln!(writer, "{},histogram,{},{}", metric, value, count)?;
+    }
+    Ok(())
+}
+
+pub fn write_json<W: Write>(writer: &mut W, outcomes: &[GameOutcome]) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut *writer, &SimulationSummary::from_outcomes(outcomes))?;
+    writeln!(writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use snake_core::game_mode::{DeathCause, GameOverReason};
+
+    use crate::simulation::{GameEnd, GameOutcome};
+
+    use super::{write_csv, write_summary_csv, Distribution, SimulationSummary};
+
+    fn outcome(final_length: usize, ticks: u64, end: GameEnd) -> GameOutcome {
+        GameOutcome {
+            seed: 0,
+            final_length,
+            ticks,
+            score: final_length as u32 - 3,
+            end,
+        }
+    }
+
+    #[test]
+    fn test_should_compute_distribution_percentiles_and_histogram() {
+        let distribution =
+            Distribution::from_values((1..=10).rev()).expect("values should not be empty");
+        assert_eq!(1, distribution.min);
+        assert_eq!(10, distribution.max);
+        assert_eq!(5.5, distribution.mean);
+        assert_eq!(1, distribution.p10);
+        assert_eq!(3, distribution.p25);
+        assert_eq!(5, distribution.median);
+        assert_eq!(8, distribution.p75);
+        assert_eq!(9, distribution.p90);
+        assert_eq!(10, distribution.p99);
+        assert_eq!(Some(&1), distribution.histogram.get(&7));
+        assert_eq!(None, Distribution::from_values(std::iter::empty()));
+    }
+
+    #[test]
+    fn test_should_count_end_reasons_when_summarizing_outcomes() {
+        let self_collision = GameEnd::Finished(GameOverReason::Died(DeathCause::SelfCollision));
+        let summary = SimulationSummary::from_outcomes(&[
+            outcome(3, 10, self_collision),
+            outcome(5, 20, self_collision),
+            outcome(4, 100, GameEnd::TickLimit),
+        ]);
+        assert_eq!(3, summary.games);
+        assert_eq!(Some(&2), summary.end_reasons.get("self_collision"));
+        assert_eq!(Some(&1), summary.end_reasons.get("tick_limit"));
+        assert_eq!(Some(2), summary.score.map(|score| score.max));
+    }
+
+    #[test]
+    fn test_should_write_one_csv_row_per_game() {
+        let mut csv = vec![];
+        write_csv(&mut csv, &[outcome(3, 10, GameEnd::TickLimit)]).expect("csv should write");
+        assert_eq!(
+            "game,seed,final_length,ticks_survived,score,end_reason\n0,0,3,10,0,tick_limit\n",
+            String::from_utf8(csv).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_should_write_distributions_as_csv_rows_when_summarizing() {
+        let mut csv = vec![];
+        write_summary_csv(
+            &mut csv,
+            &[
+                outcome(3, 10, GameEnd::TickLimit),
+                outcome(5, 10, GameEnd::TickLimit),
+            ],
+        )
+        .expect("csv should write");
+        let csv = String::from_utf8(csv).unwrap();
+        let rows = csv.lines().collect::<Vec<&str>>();
+        assert_eq!("metric,kind,key,value", rows[0]);
+        assert!(rows.contains(&"games,count,all,2"));
+        assert!(rows.contains(&"final_length,stat,p90,5"));
+        assert!(rows.contains(&"ticks_survived,histogram,10,2"));
+        assert!(rows.contains(&"score,stat,mean,1"));
+        assert!(rows.contains(&"end_reason,count,tick_limit,2"));
+    }
+}
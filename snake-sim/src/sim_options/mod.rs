@@ -0,0 +1,169 @@
+use std::fmt;
+
+use snake_core::game_config::{GameConfig, ShrinkingArenaConfig};
+use snake_core::game_mode::GameMode;
+use snake_core::level_map::LevelMap;
+
+use crate::controller::ControllerKind;
+
+const DEFAULT_GAMES: usize = 1000;
+const DEFAULT_MAX_TICKS: u64 = 10000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Csv,
+    SummaryCsv,
+    Json,
+}
+
+#[derive(Debug, Clone)]
+pub struct SimOptions {
+    pub games: usize,
+    pub base_seed: u64,
+    pub threads: usize,
+    pub max_ticks: u64,
+    pub controller: ControllerKind,
+    pub format: ReportFormat,
+    pub output: Option<String>,
+    pub game_config: GameConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimOptionsError(String);
+
+impl fmt::Display for SimOptionsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+pub const USAGE: &str = "Usage: snake-sim [--games N] [--seed N] [--threads N] [--max-ticks N]
+                 [--controller straight|random|apple-chaser] [--format csv|summary-csv|json]
+                 [--output PATH] [--mode endless|time:TICKS|target:LENGTH|survival:TICKS]
+                 [--lives N] [--arena SHRINK_INTERVAL_TICKS] [--level PATH]";
+
+impl SimOptions {
+    pub fn parse<I: Iterator<Item = String>>(args: I) -> Result<Self, SimOptionsError> {
+        let mut options = SimOptions {
+            games: DEFAULT_GAMES,
+            base_seed: 0,
+            threads: std::thread::available_parallelism()
+                .map(|threads| threads.get())
+                .unwrap_or(1),
+            max_ticks: DEFAULT_MAX_TICKS,
+            controller: ControllerKind::AppleChaser,
+            format: ReportFormat::Csv,
+            output: None,
+            game_config: GameConfig::default(),
+        };
+
+        let mut args = args;
+        while let Some(flag) = args.next() {
+            let value = args
+                .next()
+                .ok_or_else(|| SimOptionsError(format!("Missing value for {}", flag)))?;
+            match flag.as_str() {
+                "--games" => options.games = parse_number(&flag, &value)?,
+                "--seed" => options.base_seed = parse_number(&flag, &value)?,
+                "--threads" => options.threads = parse_number::<usize>(&flag, &value)?.max(1),
+                "--max-ticks" => options.max_ticks = parse_number(&flag, &value)?,
+                "--controller" => {
+                    options.controller = ControllerKind::try_from(value.as_str())
+                        .map_err(|_| SimOptionsError(format!("Unknown controller {}", value)))?
+                }
+                "--format" => {
+                    options.format = match value.as_str() {
+                        "csv" => ReportFormat::Csv,
+                        "summary-csv" => ReportFormat::SummaryCsv,
+                        "json" => ReportFormat::Json,
+                        _ => return Err(SimOptionsError(format!("Unknown format {}", value))),
+                    }
+                }
+                "--output" => options.output = Some(value),
+                "--mode" => options.game_config.mode = parse_mode(&value)?,
+                "--lives" => options.game_config.lives = parse_number(&flag, &value)?,
+                "--arena" => {
+                    options.game_config.shrinking_arena = Some(ShrinkingArenaConfig {
+                        shrink_interval_ticks: parse_number(&flag, &value)?,
+                    })
+                }
+                "--level" => options.game_config.level = Some(read_level(&value)?),
+                _ => return Err(SimOptionsError(format!("Unknown option {}", flag))),
+            }
+        }
+        if let Some(level) = &options.game_config.level {
+            level.validate(&options.game_config.board).map_err(|error| {
+                SimOptionsError(format!("Level does not fit the board: {:?}", error))
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, SimOptionsError> {
+    value
+        .parse()
+        .map_err(|_| SimOptionsError(format!("Invalid number {} for {}", value, flag)))
+}
+
+fn read_level(path: &str) -> Result<LevelMap, SimOptionsError> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|error| SimOptionsError(format!("Cannot read level {}: {}", path, error)))?;
+    LevelMap::parse(&text)
+        .map_err(|error| SimOptionsError(format!("Invalid level {}: {:?}", path, error)))
+}
+
+fn parse_mode(value: &str) -> Result<GameMode, SimOptionsError> {
+    let (name, argument) = value.split_once(':').unwrap_or((value, ""));
+    match name {
+        "endless" => Ok(GameMode::Endless),
+        "time" => Ok(GameMode::TimeLimited {
+            tick_budget: parse_number("--mode time", argument)?,
+        }),
+        "target" => Ok(GameMode::TargetLength {
+            target_length: parse_number("--mode target", argument)?,
+        }),
+        "survival" => Ok(GameMode::Survival {
+            hazard_interval_ticks: parse_number("--mode survival", argument)?,
+        }),
+        _ => Err(SimOptionsError(format!("Unknown mode {}", value))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use snake_core::game_mode::GameMode;
+
+    use crate::controller::ControllerKind;
+
+    use super::{ReportFormat, SimOptions};
+
+    fn parse(args: &[&str]) -> Result<SimOptions, super::SimOptionsError> {
+        SimOptions::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_should_parse_all_options_when_provided() {
+        let options = parse(&[
+            "--games", "20", "--seed", "9", "--controller", "random", "--format", "json",
+            "--mode", "target:12", "--lives", "3",
+        ])
+        .expect("options should parse");
+        assert_eq!(20, options.games);
+        assert_eq!(9, options.base_seed);
+        assert_eq!(ControllerKind::Random, options.controller);
+        assert_eq!(ReportFormat::Json, options.format);
+        assert_eq!(
+            GameMode::TargetLength { target_length: 12 },
+            options.game_config.mode
+        );
+        assert_eq!(3, options.game_config.lives);
+    }
+
+    #[test]
+    fn test_should_reject_unknown_option_and_missing_value() {
+        assert!(parse(&["--speed", "3"]).is_err());
+        assert!(parse(&["--games"]).is_err());
+        assert!(parse(&["--mode", "time:soon"]).is_err());
+    }
+}
@@ -0,0 +1,157 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use snake_core::game_config::GameConfig;
+use snake_core::game_mode::{DeathCause, GameOverReason, GameResult};
+use snake_core::SnakeContext;
+
+use crate::controller::ControllerKind;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameEnd {
+    Finished(GameOverReason),
+    TickLimit,
+}
+
+impl GameEnd {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameEnd::Finished(GameOverReason::Died(DeathCause::SelfCollision)) => "self_collision",
+            GameEnd::Finished(GameOverReason::Died(DeathCause::WallCollision)) => "wall_collision",
+            GameEnd::Finished(GameOverReason::Died(DeathCause::CaughtByArena)) => {
+                "caught_by_arena"
+            }
+            GameEnd::Finished(GameOverReason::Died(DeathCause::CreatureContact)) => {
+                "creature_contact"
+            }
+            GameEnd::Finished(GameOverReason::Died(DeathCause::SnakeCollision)) => {
+                "snake_collision"
+            }
+            GameEnd::Finished(GameOverReason::TimeUp) => "time_up",
+            GameEnd::Finished(GameOverReason::TargetReached) => "target_reached",
+            GameEnd::TickLimit => "tick_limit",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameOutcome {
+    pub seed: u64,
+    pub final_length: usize,
+    pub ticks: u64,
+    pub score: u32,
+    pub end: GameEnd,
+}
+
+impl From<(u64, GameResult)> for GameOutcome {
+    fn from((seed, result): (u64, GameResult)) -> Self {
+        Self {
+            seed,
+            final_length: result.final_length,
+            ticks: result.stats.ticks,
+            score: result.score,
+            end: GameEnd::Finished(result.reason),
+        }
+    }
+}
+
+pub fn run_game(
+    game_config: &GameConfig,
+    controller: ControllerKind,
+    seed: u64,
+    max_ticks: u64,
+) -> GameOutcome {
+    let mut game = SnakeContext::with_config(GameConfig {
+        seed: Some(seed),
+        ..game_config.clone()
+    });
+    game.new_game();
+    let mut controller = controller.create(seed);
+
+    for _ in 0..max_ticks {
+        if let Some(direction) = controller.next_direction(&game) {
+            let _ = game.change_direction(direction);
+        }
+        if let Some(result) = game.update_position().game_over {
+            return GameOutcome::from((seed, result));
+        }
+    }
+
+    GameOutcome {
+        seed,
+        final_length: game.snake_segments().count(),
+        ticks: game.stats().ticks,
+        score: game.score(),
+        end: GameEnd::TickLimit,
+    }
+}
+
+pub fn run_games(
+    game_config: &GameConfig,
+    controller: ControllerKind,
+    base_seed: u64,
+    games: usize,
+    max_ticks: u64,
+    threads: usize,
+) -> Vec<GameOutcome> {
+    let next_game = Arc::new(AtomicUsize::new(0));
+    let workers = (0..threads.max(1))
+        .map(|_| {
+            let next_game = next_game.clone();
+            let game_config = game_config.clone();
+            thread::spawn(move || {
+                let mut outcomes = vec![];
+                loop {
+                    let index = next_game.fetch_add(1, Ordering::Relaxed);
+                    if index >= games {
+                        return outcomes;
+                    }
+                    let seed = base_seed.wrapping_add(index as u64);
+                    outcomes.push((
+                        index,
+                        run_game(&game_config, controller, seed, max_ticks),
+                    ));
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut outcomes = workers
+        .into_iter()
+        .flat_map(|worker| worker.join().expect("Simulation worker panicked"))
+        .collect::<Vec<_>>();
+    outcomes.sort_by_key(|(index, _)| *index);
+    outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use snake_core::game_config::GameConfig;
+    use snake_core::game_mode::GameMode;
+
+    use crate::controller::ControllerKind;
+
+    use super::{run_game, run_games, GameEnd};
+
+    #[test]
+    fn test_should_return_same_outcomes_for_same_seeds_regardless_of_threads() {
+        let config = GameConfig::default();
+        let single = run_games(&config, ControllerKind::Random, 42, 16, 500, 1);
+        let parallel = run_games(&config, ControllerKind::Random, 42, 16, 500, 4);
+        assert_eq!(single, parallel);
+        assert_eq!(16, parallel.len());
+    }
+
+    #[test]
+    fn test_should_stop_at_tick_budget_when_time_limited_mode() {
+        let config = GameConfig {
+            mode: GameMode::TimeLimited { tick_budget: 30 },
+            ..GameConfig::default()
+        };
+        let outcome = run_game(&config, ControllerKind::Straight, 1, 1000);
+        assert_eq!(30, outcome.ticks);
+        assert_eq!("time_up", outcome.end.label());
+        assert_ne!(GameEnd::TickLimit, outcome.end);
+    }
+}
@@ -0,0 +1,41 @@
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopControl {
+    Redraw,
+    Ignore,
+    Quit,
+}
+
+pub trait TerminalMode {
+    fn handle_key(&mut self, key: KeyCode) -> LoopControl;
+    fn tick(&mut self) -> LoopControl;
+    fn tick_interval(&self) -> Duration;
+    fn draw<W: Write>(&self, out: &mut W) -> io::Result<()>;
+}
+
+pub fn run<M: TerminalMode, W: Write>(mode: &mut M, out: &mut W) -> io::Result<()> {
+    mode.draw(out)?;
+    let mut next_tick = Instant::now() + mode.tick_interval();
+    loop {
+        let timeout = next_tick.saturating_duration_since(Instant::now());
+        let control = if event::poll(timeout)? {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => mode.handle_key(key.code),
+                _ => LoopControl::Ignore,
+            }
+        } else {
+            next_tick = Instant::now() + mode.tick_interval();
+            mode.tick()
+        };
+
+        match control {
+            LoopControl::Redraw => mode.draw(out)?,
+            LoopControl::Ignore => (),
+            LoopControl::Quit => return Ok(()),
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crossterm::cursor::MoveTo;
+use crossterm::event::KeyCode;
+use crossterm::queue;
+use crossterm::style::Print;
+use crossterm::terminal::{Clear, ClearType};
+
+use snake_core::ansi_renderer::{render_ansi_players, AnsiPalette};
+use snake_core::game_config::GameConfig;
+use snake_core::multi_snake::MultiSnakeContext;
+use snake_core::random_seed;
+
+use crate::game_loop::{LoopControl, TerminalMode};
+use crate::key_bindings::{hot_seat_command_for_key, HotSeatCommand, PlayerCommand};
+
+const PLAYERS: usize = 2;
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundOutcome {
+    Winner(usize),
+    Draw,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchScore {
+    pub best_of: u32,
+    pub round: u32,
+    pub wins: [u32; 2],
+}
+
+impl MatchScore {
+    pub fn new(best_of: u32) -> Self {
+        Self {
+            best_of: best_of.max(1),
+            round: 1,
+            wins: [0, 0],
+        }
+    }
+
+    pub fn record(&mut self, outcome: RoundOutcome) {
+        if let RoundOutcome::Winner(player) = outcome {
+            self.wins[player] += 1;
+        }
+        self.round += 1;
+    }
+
+    pub fn match_winner(&self) -> Option<usize> {
+        let rounds_to_win = self.best_of / 2 + 1;
+        self.wins.iter().position(|&wins| wins >= rounds_to_win)
+    }
+}
+
+// A round ends as soon as a player runs out of lives. If both do on the same
+// tick, the higher score takes the round.
+pub fn round_outcome(game_over: [bool; 2], scores: [u32; 2]) -> Option<RoundOutcome> {
+    match game_over {
+        [false, false] => None,
+        [true, false] => Some(RoundOutcome::Winner(1)),
+        [false, true] => Some(RoundOutcome::Winner(0)),
+        [true, true] if scores[0] > scores[1] => Some(RoundOutcome::Winner(0)),
+        [true, true] if scores[1] > scores[0] => Some(RoundOutcome::Winner(1)),
+        [true, true] => Some(RoundOutcome::Draw),
+    }
+}
+
+struct Player {
+    name: &'static str,
+    controls: &'static str,
+    palette: AnsiPalette,
+}
+
+// Both players share one board and can run into each other.
+pub struct HotSeatMatch {
+    game_config: GameConfig,
+    game: MultiSnakeContext,
+    players: [Player; 2],
+    score: MatchScore,
+    last_round: Option<RoundOutcome>,
+    round_finished: bool,
+    paused: bool,
+}
+
+impl HotSeatMatch {
+    pub fn new(game_config: GameConfig, best_of: u32) -> Self {
+        let mut game_config = game_config;
+        game_config.seed.get_or_insert_with(random_seed);
+        Self {
+            game: MultiSnakeContext::new(round_config(&game_config, 1), PLAYERS),
+            players: [
+                Player {
+                    name: "Player 1",
+                    controls: "WASD",
+                    palette: AnsiPalette::default(),
+                },
+                Player {
+                    name: "Player 2",
+                    controls: "arrows",
+                    palette: AnsiPalette {
+                        body: "\x1b[34m",
+                        head: "\x1b[94m",
+                        ..AnsiPalette::default()
+                    },
+                },
+            ],
+            game_config,
+            score: MatchScore::new(best_of),
+            last_round: None,
+            round_finished: false,
+            paused: false,
+        }
+    }
+
+    fn start_round(&mut self) {
+        self.game = MultiSnakeContext::new(
+            round_config(&self.game_config, self.score.round),
+            PLAYERS,
+        );
+        self.round_finished = false;
+        self.paused = false;
+    }
+
+    fn status_line(&self, index: usize) -> String {
+        let player = &self.players[index];
+        let snake = self.game.snake(index);
+        format!(
+            "{}{} ({}){}: score {} lives {}",
+            player.palette.head,
+            player.name,
+            player.controls,
+            RESET,
+            snake.score(),
+            snake.lives_left()
+        )
+    }
+}
+
+// Every round gets its own seed derived from the match seed, so a seeded match
+// replays identically.
+fn round_config(game_config: &GameConfig, round: u32) -> GameConfig {
+    GameConfig {
+        seed: game_config.seed.map(|seed| seed.wrapping_add(round as u64)),
+        ..game_config.clone()
+    }
+}
+
+impl TerminalMode for HotSeatMatch {
+    fn handle_key(&mut self, key: KeyCode) -> LoopControl {
+        match hot_seat_command_for_key(key) {
+            Some(HotSeatCommand::Turn(player, direction))
+                if !self.paused && !self.round_finished =>
+            {
+                let _ = self.game.change_direction(player, direction);
+                LoopControl::Ignore
+            }
+            Some(HotSeatCommand::Turn(..)) | None => LoopControl::Ignore,
+            Some(HotSeatCommand::Shared(PlayerCommand::TogglePause)) => {
+                if self.round_finished && self.score.match_winner().is_none() {
+                    self.start_round();
+                } else if !self.round_finished {
+                    self.paused = !self.paused;
+                }
+                LoopControl::Redraw
+            }
+            Some(HotSeatCommand::Shared(PlayerCommand::Restart)) => {
+                self.score = MatchScore::new(self.score.best_of);
+                self.last_round = None;
+                self.start_round();
+                LoopControl::Redraw
+            }
+            Some(HotSeatCommand::Shared(PlayerCommand::Quit)) => LoopControl::Quit,
+            Some(HotSeatCommand::Shared(PlayerCommand::Turn(_))) => LoopControl::Ignore,
+        }
+    }
+
+    fn tick(&mut self) -> LoopControl {
+        if self.paused || self.round_finished {
+            return LoopControl::Ignore;
+        }
+
+        // When the game mode ends the round, the higher score takes it.
+        let mode_over = self.game.update_positions().game_over.is_some();
+        let game_over = [0, 1].map(|index| mode_over || self.game.snake(index).is_eliminated());
+        let scores = [0, 1].map(|index| self.game.snake(index).score());
+        if let Some(outcome) = round_outcome(game_over, scores) {
+            self.score.record(outcome);
+            self.last_round = Some(outcome);
+            self.round_finished = true;
+        }
+        LoopControl::Redraw
+    }
+
+    fn tick_interval(&self) -> Duration {
+        Duration::from_millis(self.game.tick_interval_milliseconds())
+    }
+
+    fn draw<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        queue!(out, MoveTo(0, 0), Clear(ClearType::All))?;
+        let palettes = [self.players[0].palette, self.players[1].palette];
+        for line in render_ansi_players(&self.game, &palettes).lines() {
+            queue!(out, Print(line), Print("\r\n"))?;
+        }
+
+        queue!(
+            out,
+            Print(format!("{}\r\n", self.status_line(0))),
+            Print(format!("{}\r\n", self.status_line(1))),
+            Print(format!(
+                "Match (best of {}): {} - {}\r\n",
+                self.score.best_of, self.score.wins[0], self.score.wins[1]
+            ))
+        )?;
+
+        match (self.score.match_winner(), self.last_round) {
+            (Some(winner), _) => queue!(
+                out,
+                Print(format!(
+                    "{} wins the match! r for a rematch, q to quit\r\n",
+                    self.players[winner].name
+                ))
+            )?,
+            (None, Some(outcome)) if self.round_finished => {
+                let message = match outcome {
+                    RoundOutcome::Winner(winner) => {
+                        format!("{} takes the round!", self.players[winner].name)
+                    }
+                    RoundOutcome::Draw => "The round is a draw!".to_string(),
+                };
+                queue!(
+                    out,
+                    Print(format!(
+                        "{} Press space for round {}\r\n",
+                        message, self.score.round
+                    ))
+                )?
+            }
+            _ if self.paused => queue!(out, Print("PAUSED - press p to resume\r\n"))?,
+            _ => queue!(out, Print(format!("Round {}\r\n", self.score.round)))?,
+        }
+        queue!(out, Print("p pause, r restart match, q quit\r\n"))?;
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{round_outcome, MatchScore, RoundOutcome};
+
+    #[test]
+    fn test_should_give_round_to_survivor_or_higher_score() {
+        assert_eq!(None, round_outcome([false, false], [3, 1]));
+        assert_eq!(
+            Some(RoundOutcome::Winner(1)),
+            round_outcome([true, false], [9, 1])
+        );
+        assert_eq!(
+            Some(RoundOutcome::Winner(0)),
+            round_outcome([true, true], [4, 2])
+        );
+        assert_eq!(
+            Some(RoundOutcome::Draw),
+            round_outcome([true, true], [2, 2])
+        );
+    }
+
+    #[test]
+    fn test_should_declare_match_winner_after_majority_of_rounds() {
+        let mut score = MatchScore::new(3);
+        score.record(RoundOutcome::Winner(1));
+        score.record(RoundOutcome::Draw);
+        assert_eq!(None, score.match_winner());
+        score.record(RoundOutcome::Winner(1));
+        assert_eq!(Some(1), score.match_winner());
+        assert_eq!(4, score.round);
+    }
+}
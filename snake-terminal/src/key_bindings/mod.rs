@@ -0,0 +1,96 @@
+use crossterm::event::KeyCode;
+
+use snake_core::Direction;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerCommand {
+    Turn(Direction),
+    TogglePause,
+    Restart,
+    Quit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotSeatCommand {
+    Turn(usize, Direction),
+    Shared(PlayerCommand),
+}
+
+pub fn command_for_key(key: KeyCode) -> Option<PlayerCommand> {
+    match key {
+        KeyCode::Up | KeyCode::Char('w') | KeyCode::Char('W') => {
+            Some(PlayerCommand::Turn(Direction::Up))
+        }
+        KeyCode::Down | KeyCode::Char('s') | KeyCode::Char('S') => {
+            Some(PlayerCommand::Turn(Direction::Down))
+        }
+        KeyCode::Left | KeyCode::Char('a') | KeyCode::Char('A') => {
+            Some(PlayerCommand::Turn(Direction::Left))
+        }
+        KeyCode::Right | KeyCode::Char('d') | KeyCode::Char('D') => {
+            Some(PlayerCommand::Turn(Direction::Right))
+        }
+        KeyCode::Char('p') | KeyCode::Char(' ') => Some(PlayerCommand::TogglePause),
+        KeyCode::Char('r') => Some(PlayerCommand::Restart),
+        KeyCode::Char('q') | KeyCode::Esc => Some(PlayerCommand::Quit),
+        _ => None,
+    }
+}
+
+pub fn hot_seat_command_for_key(key: KeyCode) -> Option<HotSeatCommand> {
+    let player = match key {
+        KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => 1,
+        _ => 0,
+    };
+    match command_for_key(key)? {
+        PlayerCommand::Turn(direction) => Some(HotSeatCommand::Turn(player, direction)),
+        command => Some(HotSeatCommand::Shared(command)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use snake_core::Direction;
+
+    use super::{command_for_key, hot_seat_command_for_key, HotSeatCommand, PlayerCommand};
+
+    #[test]
+    fn test_should_map_arrows_and_wasd_to_same_turns() {
+        assert_eq!(
+            command_for_key(KeyCode::Up),
+            command_for_key(KeyCode::Char('w'))
+        );
+        assert_eq!(
+            Some(PlayerCommand::Turn(Direction::Left)),
+            command_for_key(KeyCode::Char('a'))
+        );
+        assert_eq!(
+            Some(PlayerCommand::Turn(Direction::Right)),
+            command_for_key(KeyCode::Right)
+        );
+    }
+
+    #[test]
+    fn test_should_ignore_unbound_keys() {
+        assert_eq!(None, command_for_key(KeyCode::Char('x')));
+        assert_eq!(Some(PlayerCommand::Quit), command_for_key(KeyCode::Esc));
+    }
+
+    #[test]
+    fn test_should_split_wasd_and_arrows_between_players_when_hot_seat() {
+        assert_eq!(
+            Some(HotSeatCommand::Turn(0, Direction::Up)),
+            hot_seat_command_for_key(KeyCode::Char('w'))
+        );
+        assert_eq!(
+            Some(HotSeatCommand::Turn(1, Direction::Up)),
+            hot_seat_command_for_key(KeyCode::Up)
+        );
+        assert_eq!(
+            Some(HotSeatCommand::Shared(PlayerCommand::TogglePause)),
+            hot_seat_command_for_key(KeyCode::Char('p'))
+        );
+    }
+}
@@ -0,0 +1,65 @@
+mod game_loop;
+mod hot_seat;
+mod key_bindings;
+mod single_player;
+mod terminal_options;
+
+use std::io::{self, stdout};
+use std::process::exit;
+
+use crossterm::cursor::{Hide, Show};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+
+use crate::{
+    game_loop::run,
+    hot_seat::HotSeatMatch,
+    single_player::SinglePlayerGame,
+    terminal_options::{parse_options, PlayMode, USAGE},
+};
+
+struct RawTerminalGuard;
+
+impl RawTerminalGuard {
+    fn enter() -> io::Result<Self> {
+        enable_raw_mode()?;
+        execute!(stdout(), EnterAlternateScreen, Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawTerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(stdout(), Show, LeaveAlternateScreen);
+        let _ = disable_raw_mode();
+    }
+}
+
+fn main() -> io::Result<()> {
+    if std::env::args().any(|arg| arg == "--help") {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+
+    let options = match parse_options(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}\n{}", error, USAGE);
+            exit(2);
+        }
+    };
+
+    let _guard = RawTerminalGuard::enter()?;
+    match options.play_mode {
+        PlayMode::SinglePlayer => run(
+            &mut SinglePlayerGame::new(options.game_config),
+            &mut stdout(),
+        ),
+        PlayMode::HotSeat { best_of } => run(
+            &mut HotSeatMatch::new(options.game_config, best_of),
+            &mut stdout(),
+        ),
+    }
+}
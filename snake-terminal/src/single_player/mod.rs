@@ -0,0 +1,115 @@
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crossterm::cursor::MoveTo;
+use crossterm::event::KeyCode;
+use crossterm::queue;
+use crossterm::style::Print;
+use crossterm::terminal::{Clear, ClearType};
+
+use snake_core::ansi_renderer::{render_ansi, AnsiPalette};
+use snake_core::game_config::GameConfig;
+use snake_core::game_mode::GameResult;
+use snake_core::SnakeContext;
+
+use crate::game_loop::{LoopControl, TerminalMode};
+use crate::key_bindings::{command_for_key, PlayerCommand};
+
+pub struct SinglePlayerGame {
+    game_config: GameConfig,
+    game: SnakeContext,
+    palette: AnsiPalette,
+    paused: bool,
+    last_result: Option<GameResult>,
+}
+
+impl SinglePlayerGame {
+    pub fn new(game_config: GameConfig) -> Self {
+        let game = start_game(&game_config);
+        Self {
+            game_config,
+            game,
+            palette: AnsiPalette::default(),
+            paused: false,
+            last_result: None,
+        }
+    }
+}
+
+// Restarting builds a fresh context, so a seeded game replays from the same start.
+pub fn start_game(game_config: &GameConfig) -> SnakeContext {
+    let mut game = SnakeContext::with_config(game_config.clone());
+    game.new_game();
+    game
+}
+
+impl TerminalMode for SinglePlayerGame {
+    fn handle_key(&mut self, key: KeyCode) -> LoopControl {
+        match command_for_key(key) {
+            Some(PlayerCommand::Turn(direction)) if !self.paused => {
+                let _ = self.game.change_direction(direction);
+                LoopControl::Ignore
+            }
+            Some(PlayerCommand::Turn(_)) | None => LoopControl::Ignore,
+            Some(PlayerCommand::TogglePause) => {
+                self.paused = !self.paused;
+                LoopControl::Redraw
+            }
+            Some(PlayerCommand::Restart) => {
+                self.game = start_game(&self.game_config);
+                self.last_result = None;
+                self.paused = false;
+                LoopControl::Redraw
+            }
+            Some(PlayerCommand::Quit) => LoopControl::Quit,
+        }
+    }
+
+    fn tick(&mut self) -> LoopControl {
+        if self.paused {
+            return LoopControl::Ignore;
+        }
+        if let Some(result) = self.game.update_position().game_over {
+            self.last_result = Some(result);
+        }
+        LoopControl::Redraw
+    }
+
+    fn tick_interval(&self) -> Duration {
+        Duration::from_millis(self.game.tick_interval_milliseconds())
+    }
+
+    fn draw<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        queue!(out, MoveTo(0, 0), Clear(ClearType::All))?;
+        for line in render_ansi(&self.game, &self.palette).lines() {
+            queue!(out, Print(line), Print("\r\n"))?;
+        }
+        queue!(
+            out,
+            Print(format!(
+                "Score: {}  Length: {}  Lives: {}  Seed: {}\r\n",
+                self.game.score(),
+                self.game.snake_segments().count(),
+                self.game.lives_left(),
+                self.game.seed()
+            ))
+        )?;
+        if self.paused {
+            queue!(out, Print("PAUSED - press p to resume\r\n"))?;
+        }
+        if let Some(result) = self.last_result {
+            queue!(
+                out,
+                Print(format!(
+                    "Last game over: {:?}, score {}\r\n",
+                    result.reason, result.score
+                ))
+            )?;
+        }
+        queue!(
+            out,
+            Print("arrows/WASD turn, p pause, r restart, q quit\r\n")
+        )?;
+        out.flush()
+    }
+}
@@ -0,0 +1,168 @@
+use std::fmt;
+
+use snake_core::game_config::{speed_policy::SpeedPolicy, GameConfig};
+use snake_core::level_map::LevelMap;
+use snake_core::visibility::{DistanceMetric, VisibilityConfig};
+
+const DEFAULT_TICK_MILLISECONDS: u64 = 200;
+const DEFAULT_BEST_OF: u32 = 3;
+
+pub const USAGE: &str =
+    "Usage: snake-terminal [--tick-ms N] [--seed N] [--lives N] [--players 1|2] [--best-of N]
+                     [--fog RADIUS] [--fog-euclidean RADIUS] [--level PATH]
+
+Controls: arrows/WASD turn, p/space pause, r restart, q/Esc quit
+Hot-seat (--players 2): both snakes share one board, player 1 uses WASD, player 2 arrows";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayMode {
+    SinglePlayer,
+    HotSeat { best_of: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerminalOptions {
+    pub game_config: GameConfig,
+    pub play_mode: PlayMode,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerminalOptionsError(String);
+
+impl fmt::Display for TerminalOptionsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+pub fn parse_options<I: Iterator<Item = String>>(
+    args: I,
+) -> Result<TerminalOptions, TerminalOptionsError> {
+    let mut players = 1;
+    let mut best_of = DEFAULT_BEST_OF;
+    let mut game_config = GameConfig {
+        speed_policy: SpeedPolicy::Constant {
+            interval_milliseconds: DEFAULT_TICK_MILLISECONDS,
+        },
+        ..GameConfig::default()
+    };
+
+    let mut args = args;
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| TerminalOptionsError(format!("Missing value for {}", flag)))?;
+        if flag == "--level" {
+            game_config.level = Some(read_level(&value)?);
+            continue;
+        }
+        let number = value
+            .parse::<u64>()
+            .map_err(|_| TerminalOptionsError(format!("Invalid number {} for {}", value, flag)))?;
+        match flag.as_str() {
+            "--tick-ms" => {
+                game_config.speed_policy = SpeedPolicy::Constant {
+                    interval_milliseconds: number.max(1),
+                }
+            }
+            "--seed" => game_config.seed = Some(number),
+            "--lives" => game_config.lives = number.max(1) as u32,
+            // A radius of 0 turns the fog off, the same as in the C interface.
+            "--fog" | "--fog-euclidean" => {
+                game_config.visibility = (number > 0).then_some(VisibilityConfig {
+                    radius: number as u32,
+                    metric: match flag.as_str() {
+                        "--fog" => DistanceMetric::Manhattan,
+                        _ => DistanceMetric::Euclidean,
+                    },
+                })
+            }
+            "--players" if number == 1 || number == 2 => players = number,
+            "--best-of" => best_of = number.max(1) as u32,
+            _ => return Err(TerminalOptionsError(format!("Unknown option {}", flag))),
+        }
+    }
+
+    if let Some(level) = &game_config.level {
+        level.validate(&game_config.board).map_err(|error| {
+            TerminalOptionsError(format!("Level does not fit the board: {:?}", error))
+        })?;
+    }
+
+    let play_mode = match players {
+        2 => PlayMode::HotSeat { best_of },
+        _ => PlayMode::SinglePlayer,
+    };
+    Ok(TerminalOptions {
+        game_config,
+        play_mode,
+    })
+}
+
+fn read_level(path: &str) -> Result<LevelMap, TerminalOptionsError> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|error| TerminalOptionsError(format!("Cannot read level {}: {}", path, error)))?;
+    LevelMap::parse(&text)
+        .map_err(|error| TerminalOptionsError(format!("Invalid level {}: {:?}", path, error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use snake_core::game_config::speed_policy::SpeedPolicy;
+
+    use snake_core::visibility::{DistanceMetric, VisibilityConfig};
+
+    use super::{parse_options, PlayMode, TerminalOptions, TerminalOptionsError};
+
+    fn parse(args: &[&str]) -> Result<TerminalOptions, TerminalOptionsError> {
+        parse_options(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_should_build_game_config_from_options() {
+        let options = parse(&["--tick-ms", "120", "--seed", "5", "--lives", "3"])
+            .expect("options should parse");
+        assert_eq!(PlayMode::SinglePlayer, options.play_mode);
+        let config = options.game_config;
+        assert_eq!(
+            SpeedPolicy::Constant {
+                interval_milliseconds: 120
+            },
+            config.speed_policy
+        );
+        assert_eq!(Some(5), config.seed);
+        assert_eq!(3, config.lives);
+    }
+
+    #[test]
+    fn test_should_enable_fog_of_war_with_selected_metric() {
+        let options = parse(&["--fog-euclidean", "4"]).expect("options should parse");
+        assert_eq!(
+            Some(VisibilityConfig {
+                radius: 4,
+                metric: DistanceMetric::Euclidean
+            }),
+            options.game_config.visibility
+        );
+    }
+
+    #[test]
+    fn test_should_disable_fog_of_war_when_radius_is_zero() {
+        let options = parse(&["--fog", "3", "--fog", "0"]).expect("options should parse");
+        assert_eq!(None, options.game_config.visibility);
+    }
+
+    #[test]
+    fn test_should_select_hot_seat_mode_when_two_players() {
+        let options = parse(&["--players", "2", "--best-of", "5"]).expect("options should parse");
+        assert_eq!(PlayMode::HotSeat { best_of: 5 }, options.play_mode);
+    }
+
+    #[test]
+    fn test_should_reject_invalid_options() {
+        assert!(parse(&["--players", "3"]).is_err());
+        assert!(parse(&["--tick-ms", "fast"]).is_err());
+        assert!(parse(&["--color", "1"]).is_err());
+        assert!(parse(&["--seed"]).is_err());
+    }
+}
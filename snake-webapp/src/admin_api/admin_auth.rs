@@ -0,0 +1,57 @@
+use std::future::{ready, Ready};
+
+use actix_web::dev::Payload;
+use actix_web::web::Data;
+use actix_web::{FromRequest, HttpRequest};
+
+use crate::admin_api::audit_log::AuditLog;
+use crate::http::error::UnauthorizedError;
+
+// `None` leaves the admin API locked for everyone.
+pub struct AdminToken(pub Option<String>);
+
+pub struct AdminCaller {
+    pub remote_addr: String,
+}
+
+impl FromRequest for AdminCaller {
+    type Error = UnauthorizedError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(request: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let remote_addr = request
+            .peer_addr()
+            .map(|address| address.to_string())
+            .unwrap_or_else(|| "unknown".to_owned());
+        let expected = request
+            .app_data::<Data<AdminToken>>()
+            .and_then(|token| token.0.clone());
+        let provided = request
+            .headers()
+            .get("Authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+
+        let authorized = match (expected, provided) {
+            (Some(expected), Some(provided)) => tokens_match(&expected, provided),
+            _ => false,
+        };
+        if !authorized {
+            if let Some(audit_log) = request.app_data::<Data<AuditLog>>() {
+                audit_log.record_rejected(&remote_addr, request.path());
+            }
+            return ready(Err(UnauthorizedError));
+        }
+        ready(Ok(AdminCaller { remote_addr }))
+    }
+}
+
+// Compares every byte so the response time does not reveal the matching prefix.
+fn tokens_match(expected: &str, provided: &str) -> bool {
+    expected.len() == provided.len()
+        && expected
+            .bytes()
+            .zip(provided.bytes())
+            .fold(0, |difference, (left, right)| difference | (left ^ right))
+            == 0
+}
@@ -0,0 +1,98 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+const AUDIT_LOG_CAPACITY: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuditEntry {
+    pub at_unix_ms: u64,
+    pub remote_addr: String,
+    pub action: &'static str,
+    pub argument: Option<String>,
+    pub outcome: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuditLogResponse {
+    pub entries: Vec<AuditEntry>,
+    pub rejected_requests: u64,
+}
+
+// Keeps the most recent admin actions in memory, oldest first. Rejected
+// requests are only counted, so guessing tokens cannot push real actions out.
+pub struct AuditLog {
+    entries: Mutex<VecDeque<AuditEntry>>,
+    capacity: usize,
+    rejected_requests: AtomicU64,
+}
+
+impl AuditLog {
+    pub fn new() -> Self {
+        Self::with_capacity(AUDIT_LOG_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+            rejected_requests: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record(
+        &self,
+        remote_addr: &str,
+        action: &'static str,
+        argument: Option<String>,
+        outcome: &str,
+    ) {
+        log::info!(
+            "admin {} {} from {}: {}",
+            action,
+            argument.as_deref().unwrap_or("-"),
+            remote_addr,
+            outcome
+        );
+        let at_unix_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or(0);
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() == self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(AuditEntry {
+            at_unix_ms,
+            remote_addr: remote_addr.to_owned(),
+            action,
+            argument,
+            outcome: outcome.to_owned(),
+        });
+    }
+
+    // Logs the 1st, 2nd, 4th, 8th... rejection so a flood of bad tokens
+    // cannot flood the log as well.
+    pub fn record_rejected(&self, remote_addr: &str, path: &str) {
+        let rejected = self.rejected_requests.fetch_add(1, Ordering::Relaxed) + 1;
+        if rejected.is_power_of_two() {
+            log::warn!(
+                "rejected admin request to {} from {} ({} rejected so far)",
+                path,
+                remote_addr,
+                rejected
+            );
+        }
+    }
+
+    pub fn entries(&self) -> Vec<AuditEntry> {
+        self.entries.lock().unwrap().iter().cloned().collect()
+    }
+
+    pub fn rejected_requests(&self) -> u64 {
+        self.rejected_requests.load(Ordering::Relaxed)
+    }
+}
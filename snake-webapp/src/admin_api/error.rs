@@ -0,0 +1,7 @@
+use crate::{http::error::ServiceUnavailableError, next_move_scheduler::SchedulerStoppedError};
+
+impl From<SchedulerStoppedError> for ServiceUnavailableError {
+    fn from(_: SchedulerStoppedError) -> Self {
+        ServiceUnavailableError::new("Scheduler is not running")
+    }
+}
@@ -0,0 +1,322 @@
+pub mod admin_auth;
+pub mod audit_log;
+mod error;
+
+use actix_web::web::{Data, Json, Path};
+use actix_web::{delete, get, post, Error};
+
+use crate::app_settings::{MAX_TICK_INTERVAL_MILLISECONDS, MIN_TICK_INTERVAL_MILLISECONDS};
+use crate::http::error::{BadRequestError, NotFoundError, ServiceUnavailableError};
+use crate::model::direction_request::DirectionRequest;
+use crate::next_move_scheduler::{SchedulerCommand, SchedulerHandle, SchedulerStatus};
+
+use self::admin_auth::AdminCaller;
+use self::audit_log::{AuditLog, AuditLogResponse};
+
+async fn run_command(
+    caller: AdminCaller,
+    scheduler: Data<SchedulerHandle>,
+    audit_log: Data<AuditLog>,
+    action: &'static str,
+    argument: Option<String>,
+    command: SchedulerCommand,
+) -> Result<Json<SchedulerStatus>, ServiceUnavailableError> {
+    match scheduler.send(command).await {
+        Ok(status) => {
+            audit_log.record(&caller.remote_addr, action, argument, "ok");
+            Ok(Json(status))
+        }
+        Err(stopped) => {
+            audit_log.record(&caller.remote_addr, action, argument, "scheduler stopped");
+            Err(stopped.into())
+        }
+    }
+}
+
+#[post("/pause")]
+async fn pause(
+    caller: AdminCaller,
+    scheduler: Data<SchedulerHandle>,
+    audit_log: Data<AuditLog>,
+) -> Result<Json<SchedulerStatus>, ServiceUnavailableError> {
+    run_command(
+        caller,
+        scheduler,
+        audit_log,
+        "pause",
+        None,
+        SchedulerCommand::Pause,
+    )
+    .await
+}
+
+#[post("/resume")]
+async fn resume(
+    caller: AdminCaller,
+    scheduler: Data<SchedulerHandle>,
+    audit_log: Data<AuditLog>,
+) -> Result<Json<SchedulerStatus>, ServiceUnavailableError> {
+    run_command(
+        caller,
+        scheduler,
+        audit_log,
+        "resume",
+        None,
+        SchedulerCommand::Resume,
+    )
+    .await
+}
+
+#[post("/step")]
+async fn step(
+    caller: AdminCaller,
+    scheduler: Data<SchedulerHandle>,
+    audit_log: Data<AuditLog>,
+) -> Result<Json<SchedulerStatus>, ServiceUnavailableError> {
+    run_command(
+        caller,
+        scheduler,
+        audit_log,
+        "step",
+        None,
+        SchedulerCommand::Step,
+    )
+    .await
+}
+
+#[post("/reset")]
+async fn reset(
+    caller: AdminCaller,
+    scheduler: Data<SchedulerHandle>,
+    audit_log: Data<AuditLog>,
+) -> Result<Json<SchedulerStatus>, ServiceUnavailableError> {
+    run_command(
+        caller,
+        scheduler,
+        audit_log,
+        "reset",
+        None,
+        SchedulerCommand::Reset,
+    )
+    .await
+}
+
+#[post("/tick-interval/{milliseconds}")]
+async fn set_tick_interval(
+    caller: AdminCaller,
+    scheduler: Data<SchedulerHandle>,
+    audit_log: Data<AuditLog>,
+    milliseconds: Path<u64>,
+) -> Result<Json<SchedulerStatus>, Error> {
+    let milliseconds = milliseconds.into_inner();
+    let argument = Some(milliseconds.to_string());
+    if !(MIN_TICK_INTERVAL_MILLISECONDS..=MAX_TICK_INTERVAL_MILLISECONDS).contains(&milliseconds) {
+        audit_log.record(
+            &caller.remote_addr,
+            "set_tick_interval",
+            argument,
+            "out of range",
+        );
+        return Err(BadRequestError::new("Tick interval out of range").into());
+    }
+
+    let command = SchedulerCommand::SetTickInterval(Some(milliseconds));
+    Ok(run_command(
+        caller,
+        scheduler,
+        audit_log,
+        "set_tick_interval",
+        argument,
+        command,
+    )
+    .await?)
+}
+
+#[delete("/tick-interval")]
+async fn clear_tick_interval(
+    caller: AdminCaller,
+    scheduler: Data<SchedulerHandle>,
+    audit_log: Data<AuditLog>,
+) -> Result<Json<SchedulerStatus>, ServiceUnavailableError> {
+    run_command(
+        caller,
+        scheduler,
+        audit_log,
+        "clear_tick_interval",
+        None,
+        SchedulerCommand::SetTickInterval(None),
+    )
+    .await
+}
+
+#[post("/direction/{path}")]
+async fn force_direction(
+    caller: AdminCaller,
+    scheduler: Data<SchedulerHandle>,
+    audit_log: Data<AuditLog>,
+    path: Path<String>,
+) -> Result<Json<SchedulerStatus>, Error> {
+    let argument = Some(path.clone());
+    let direction: DirectionRequest = match path.into_inner().try_into() {
+        Ok(direction) => direction,
+        Err(unknown_direction_err) => {
+            audit_log.record(
+                &caller.remote_addr,
+                "force_direction",
+                argument,
+                "unknown direction",
+            );
+            return Err(NotFoundError::from(unknown_direction_err).into());
+        }
+    };
+
+    let command = SchedulerCommand::ForceDirection(direction);
+    Ok(run_command(
+        caller,
+        scheduler,
+        audit_log,
+        "force_direction",
+        argument,
+        command,
+    )
+    .await?)
+}
+
+#[get("/audit")]
+async fn show_audit_log(_caller: AdminCaller, audit_log: Data<AuditLog>) -> Json<AuditLogResponse> {
+    Json(AuditLogResponse {
+        entries: audit_log.entries(),
+        rejected_requests: audit_log.rejected_requests(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::web::{scope, Data};
+    use actix_web::{test, App};
+    use snake_core::game_config::GameConfig;
+
+    use super::admin_auth::AdminToken;
+    use super::audit_log::AuditLog;
+    use super::{clear_tick_interval, pause, set_tick_interval, show_audit_log, step};
+    use crate::app_settings::AppSettings;
+    use crate::directions_container::DirectionsContainer;
+    use crate::event_stream::EventLog;
+    use crate::live_updates::LiveUpdates;
+    use crate::next_move_scheduler::NextMoveScheduler;
+    use crate::snake_context_wrapper::SnakeWebAppContext;
+
+    #[actix_web::test]
+    async fn test_should_control_scheduler_and_audit_actions_when_token_is_valid() {
+        let snake_ctx = Data::new(SnakeWebAppContext::new(GameConfig::default()));
+        snake_ctx.new_game().await;
+        let scheduler = NextMoveScheduler::run_scheduler(
+            snake_ctx.clone(),
+            Data::new(DirectionsContainer::init_container(16, false)),
+            Data::new(LiveUpdates::new()),
+            Data::new(EventLog::new()),
+            AppSettings::default().vote_resolver(),
+        );
+        let audit_log = Data::new(AuditLog::new());
+        let app = test::init_service(
+            App::new()
+                .app_data(Data::new(scheduler.handle()))
+                .app_data(Data::new(AdminToken(Some("secret".to_owned()))))
+                .app_data(audit_log.clone())
+                .service(
+                    scope("/admin")
+                        .service(pause)
+                        .service(step)
+                        .service(show_audit_log),
+                ),
+        )
+        .await;
+        let admin_post = |path: &str, token: &str| {
+            test::TestRequest::post()
+                .uri(path)
+                .insert_header(("Authorization", format!("Bearer {}", token)))
+                .to_request()
+        };
+
+        for _ in 0..3 {
+            let response = test::call_service(&app, admin_post("/admin/pause", "wrong")).await;
+            assert_eq!(401, response.status().as_u16());
+        }
+
+        let status: serde_json::Value =
+            test::call_and_read_body_json(&app, admin_post("/admin/pause", "secret")).await;
+        assert_eq!(true, status["paused"]);
+        let tick = snake_ctx.get_game_state().await.tick;
+        let _: serde_json::Value =
+            test::call_and_read_body_json(&app, admin_post("/admin/step", "secret")).await;
+        assert_eq!(tick + 1, snake_ctx.get_game_state().await.tick);
+        assert_eq!(None, snake_ctx.get_game_state().await.next_tick_at_unix_ms);
+
+        let request = test::TestRequest::get()
+            .uri("/admin/audit")
+            .insert_header(("Authorization", "Bearer secret"))
+            .to_request();
+        let audit: serde_json::Value = test::call_and_read_body_json(&app, request).await;
+        assert_eq!(3, audit["rejected_requests"]);
+        let actions = audit["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| {
+                (
+                    entry["action"].as_str().unwrap(),
+                    entry["outcome"].as_str().unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(vec![("pause", "ok"), ("step", "ok")], actions);
+    }
+
+    #[actix_web::test]
+    async fn test_should_restore_game_pace_when_tick_interval_override_is_cleared() {
+        let snake_ctx = Data::new(SnakeWebAppContext::new(GameConfig::default()));
+        snake_ctx.new_game().await;
+        let game_pace = snake_ctx.tick_interval_milliseconds().await;
+        let scheduler = NextMoveScheduler::run_scheduler(
+            snake_ctx.clone(),
+            Data::new(DirectionsContainer::init_container(16, false)),
+            Data::new(LiveUpdates::new()),
+            Data::new(EventLog::new()),
+            AppSettings::default().vote_resolver(),
+        );
+        let audit_log = Data::new(AuditLog::new());
+        let app = test::init_service(
+            App::new()
+                .app_data(Data::new(scheduler.handle()))
+                .app_data(Data::new(AdminToken(Some("secret".to_owned()))))
+                .app_data(audit_log.clone())
+                .service(
+                    scope("/admin")
+                        .service(set_tick_interval)
+                        .service(clear_tick_interval),
+                ),
+        )
+        .await;
+
+        let request = test::TestRequest::post()
+            .uri("/admin/tick-interval/500")
+            .insert_header(("Authorization", "Bearer secret"))
+            .to_request();
+        let status: serde_json::Value = test::call_and_read_body_json(&app, request).await;
+        assert_eq!(500, status["tick_interval_milliseconds"]);
+
+        let request = test::TestRequest::delete()
+            .uri("/admin/tick-interval")
+            .insert_header(("Authorization", "Bearer secret"))
+            .to_request();
+        let status: serde_json::Value = test::call_and_read_body_json(&app, request).await;
+        assert_eq!(game_pace, status["tick_interval_milliseconds"]);
+        assert_eq!(game_pace, snake_ctx.tick_interval_milliseconds().await);
+        let actions = audit_log
+            .entries()
+            .into_iter()
+            .map(|entry| entry.action)
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["set_tick_interval", "clear_tick_interval"], actions);
+    }
+}
@@ -0,0 +1,329 @@
+use std::fmt;
+
+use actix_settings::BasicSettings;
+use actix_web::http::header::HeaderName;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::Deserialize;
+
+use snake_core::game_config::board::{
+    BoardConfig, Topology, MAX_BOARD_SIDE, MIN_BOARD_HEIGHT, MIN_BOARD_WIDTH,
+};
+use snake_core::game_config::speed_policy::{SpeedPolicy, DEFAULT_TICK_INTERVAL_MILLISECONDS};
+use snake_core::game_config::GameConfig;
+
+use crate::vote_resolver::{
+    FirstVote, Plurality, Proportional, Supermajority, TieBreak, VoteResolver,
+};
+
+pub const MIN_TICK_INTERVAL_MILLISECONDS: u64 = 100;
+pub const MAX_TICK_INTERVAL_MILLISECONDS: u64 = 3_600_000;
+const DEFAULT_VOTE_CHANNEL_CAPACITY: usize = 1024;
+const DEFAULT_VOTE_SUPERMAJORITY_PERCENT: u8 = 67;
+const DEFAULT_MAX_ROOMS: usize = 16;
+const DEFAULT_ROOM_IDLE_TIMEOUT_SECONDS: u64 = 600;
+const DEFAULT_ROOM_CREATION_COOLDOWN_SECONDS: u64 = 60;
+const MAX_VOTER_TOKEN_LENGTH: usize = 128;
+
+pub type WebAppSettings = BasicSettings<AppSettings>;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BoardTopology {
+    #[default]
+    Wrapping,
+    Walled,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum VoteStrategy {
+    #[default]
+    Plurality,
+    // The name of the original, broken sampling rule that this one replaces.
+    #[serde(alias = "random-sample")]
+    Proportional,
+    FirstVote,
+    Supermajority,
+}
+
+// Every key is optional; a missing one keeps the game's default.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct AppSettings {
+    pub tick_interval_milliseconds: u64,
+    pub board_width: u32,
+    pub board_height: u32,
+    pub topology: BoardTopology,
+    pub apple_spawn_one_in: u32,
+    pub vote_strategy: VoteStrategy,
+    pub vote_tie_break_seed: Option<u64>,
+    pub vote_supermajority_percent: u8,
+    pub vote_channel_capacity: usize,
+    pub blind_votes: bool,
+    pub admin_token: Option<String>,
+    pub voter_id_header: Option<String>,
+    pub voter_tokens: Vec<String>,
+    pub max_rooms: usize,
+    pub room_idle_timeout_seconds: u64,
+    pub room_creation_cooldown_seconds: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsError {
+    TickIntervalOutOfRange(u64),
+    BoardSizeOutOfRange { width: u32, height: u32 },
+    AppleSpawnOneInZero,
+    SupermajorityPercentOutOfRange(u8),
+    VoteChannelCapacityZero,
+    AdminTokenEmpty,
+    VoterIdHeaderInvalid,
+    VoterTokenInvalid,
+    RoomIdleTimeoutZero,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        let game_config = GameConfig::default();
+        Self {
+            tick_interval_milliseconds: DEFAULT_TICK_INTERVAL_MILLISECONDS,
+            board_width: game_config.board.width,
+            board_height: game_config.board.height,
+            topology: BoardTopology::default(),
+            apple_spawn_one_in: game_config.apple_spawn_one_in,
+            vote_strategy: VoteStrategy::default(),
+            vote_tie_break_seed: None,
+            vote_supermajority_percent: DEFAULT_VOTE_SUPERMAJORITY_PERCENT,
+            vote_channel_capacity: DEFAULT_VOTE_CHANNEL_CAPACITY,
+            blind_votes: false,
+            admin_token: None,
+            voter_id_header: None,
+            voter_tokens: Vec::new(),
+            max_rooms: DEFAULT_MAX_ROOMS,
+            room_idle_timeout_seconds: DEFAULT_ROOM_IDLE_TIMEOUT_SECONDS,
+            room_creation_cooldown_seconds: DEFAULT_ROOM_CREATION_COOLDOWN_SECONDS,
+        }
+    }
+}
+
+impl AppSettings {
+    pub fn validate(&self) -> Result<(), SettingsError> {
+        if !(MIN_TICK_INTERVAL_MILLISECONDS..=MAX_TICK_INTERVAL_MILLISECONDS)
+            .contains(&self.tick_interval_milliseconds)
+        {
+            return Err(SettingsError::TickIntervalOutOfRange(
+                self.tick_interval_milliseconds,
+            ));
+        }
+        if self.board().validate().is_err() {
+            return Err(SettingsError::BoardSizeOutOfRange {
+                width: self.board_width,
+                height: self.board_height,
+            });
+        }
+        if self.apple_spawn_one_in == 0 {
+            return Err(SettingsError::AppleSpawnOneInZero);
+        }
+        if !(51..=100).contains(&self.vote_supermajority_percent) {
+            return Err(SettingsError::SupermajorityPercentOutOfRange(
+                self.vote_supermajority_percent,
+            ));
+        }
+        if self.vote_channel_capacity == 0 {
+            return Err(SettingsError::VoteChannelCapacityZero);
+        }
+        if self
+            .admin_token
+            .as_deref()
+            .is_some_and(|token| token.trim().is_empty())
+        {
+            return Err(SettingsError::AdminTokenEmpty);
+        }
+        if self
+            .voter_id_header
+            .as_deref()
+            .is_some_and(|header| HeaderName::from_bytes(header.as_bytes()).is_err())
+        {
+            return Err(SettingsError::VoterIdHeaderInvalid);
+        }
+        if self
+            .voter_tokens
+            .iter()
+            .any(|token| token.trim().is_empty() || token.len() > MAX_VOTER_TOKEN_LENGTH)
+        {
+            return Err(SettingsError::VoterTokenInvalid);
+        }
+        if self.room_idle_timeout_seconds == 0 {
+            return Err(SettingsError::RoomIdleTimeoutZero);
+        }
+        Ok(())
+    }
+
+    pub fn game_config(&self) -> GameConfig {
+        GameConfig {
+            speed_policy: SpeedPolicy::Constant {
+                interval_milliseconds: self.tick_interval_milliseconds,
+            },
+            board: self.board(),
+            apple_spawn_one_in: self.apple_spawn_one_in,
+            ..GameConfig::default()
+        }
+    }
+
+    // Without a seed, randomness comes from the OS and plurality ties are broken deterministically.
+    pub fn vote_resolver(&self) -> Box<dyn VoteResolver + Send> {
+        let seeded_rng = self.vote_tie_break_seed.map(StdRng::seed_from_u64);
+        match self.vote_strategy {
+            VoteStrategy::Plurality => Box::new(Plurality {
+                tie_break: match seeded_rng {
+                    Some(rng) => TieBreak::Seeded(Box::new(rng)),
+                    None => TieBreak::Deterministic,
+                },
+            }),
+            VoteStrategy::Proportional => Box::new(Proportional {
+                rng: seeded_rng.unwrap_or_else(StdRng::from_entropy),
+            }),
+            VoteStrategy::FirstVote => Box::new(FirstVote),
+            VoteStrategy::Supermajority => Box::new(Supermajority {
+                percent: self.vote_supermajority_percent,
+            }),
+        }
+    }
+
+    fn board(&self) -> BoardConfig {
+        BoardConfig {
+            width: self.board_width,
+            height: self.board_height,
+            topology: match self.topology {
+                BoardTopology::Wrapping => Topology::Wrapping,
+                BoardTopology::Walled => Topology::Walled,
+            },
+        }
+    }
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsError::TickIntervalOutOfRange(value) => write!(
+                f,
+                "tick-interval-milliseconds is {}, expected {} to {}",
+                value, MIN_TICK_INTERVAL_MILLISECONDS, MAX_TICK_INTERVAL_MILLISECONDS
+            ),
+            SettingsError::BoardSizeOutOfRange { width, height } => write!(
+                f,
+                "board size is {}x{}, expected a width of {} to {} and a height of {} to {}",
+                width, height, MIN_BOARD_WIDTH, MAX_BOARD_SIDE, MIN_BOARD_HEIGHT, MAX_BOARD_SIDE
+            ),
+            SettingsError::AppleSpawnOneInZero => {
+                write!(f, "apple-spawn-one-in has to be at least 1")
+            }
+            SettingsError::SupermajorityPercentOutOfRange(value) => write!(
+                f,
+                "vote-supermajority-percent is {}, expected 51 to 100",
+                value
+            ),
+            SettingsError::VoteChannelCapacityZero => {
+                write!(f, "vote-channel-capacity has to be at least 1")
+            }
+            SettingsError::AdminTokenEmpty => {
+                write!(
+                    f,
+                    "admin-token must not be empty; remove it to disable the admin API"
+                )
+            }
+            SettingsError::VoterIdHeaderInvalid => {
+                write!(f, "voter-id-header is not a valid HTTP header name")
+            }
+            SettingsError::VoterTokenInvalid => write!(
+                f,
+                "voter-tokens have to be 1 to {} characters long",
+                MAX_VOTER_TOKEN_LENGTH
+            ),
+            SettingsError::RoomIdleTimeoutZero => {
+                write!(f, "room-idle-timeout-seconds has to be at least 1")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use snake_core::game_config::board::Topology;
+
+    use super::{AppSettings, BoardTopology, SettingsError, WebAppSettings};
+
+    const CONFIGURATION: &str = include_str!("../../Configuration.toml");
+
+    fn parse_application(application: &str) -> Result<AppSettings, String> {
+        let (actix, _) = CONFIGURATION
+            .split_once("[application]")
+            .expect("configuration should have an application table");
+        WebAppSettings::from_template(&format!("{}[application]\n{}", actix, application))
+            .map(|settings| settings.application)
+            .map_err(|error| format!("{:?}", error))
+    }
+
+    #[test]
+    fn test_should_load_valid_settings_from_shipped_configuration() {
+        let settings = WebAppSettings::from_template(CONFIGURATION)
+            .expect("Configuration.toml should parse")
+            .application;
+        assert_eq!(Ok(()), settings.validate());
+        assert_eq!(AppSettings::default(), settings);
+    }
+
+    #[test]
+    fn test_should_build_game_config_when_application_table_overrides_defaults() {
+        let settings = parse_application(
+            "tick-interval-milliseconds = 500\nboard-width = 30\nboard-height = 15\ntopology = \"walled\"\n",
+        )
+        .expect("settings should parse");
+        assert_eq!(BoardTopology::Walled, settings.topology);
+        let game_config = settings.game_config();
+        assert_eq!(
+            (30, 15),
+            (game_config.board.width, game_config.board.height)
+        );
+        assert_eq!(Topology::Walled, game_config.board.topology);
+        assert_eq!(500, game_config.speed_policy.interval_milliseconds(3, 0));
+    }
+
+    #[test]
+    fn test_should_reject_settings_when_values_are_invalid() {
+        let invalid = |application: &str| {
+            parse_application(application)
+                .expect("settings should parse")
+                .validate()
+        };
+        assert_eq!(
+            Err(SettingsError::TickIntervalOutOfRange(0)),
+            invalid("tick-interval-milliseconds = 0")
+        );
+        assert_eq!(
+            Err(SettingsError::BoardSizeOutOfRange {
+                width: 2,
+                height: 10
+            }),
+            invalid("board-width = 2")
+        );
+        assert_eq!(
+            Err(SettingsError::SupermajorityPercentOutOfRange(50)),
+            invalid("vote-strategy = \"supermajority\"\nvote-supermajority-percent = 50")
+        );
+        assert_eq!(
+            Err(SettingsError::VoteChannelCapacityZero),
+            invalid("vote-channel-capacity = 0")
+        );
+        assert_eq!(
+            Err(SettingsError::VoterIdHeaderInvalid),
+            invalid("voter-id-header = \"X Voter\"")
+        );
+        assert_eq!(
+            Err(SettingsError::VoterTokenInvalid),
+            invalid("voter-tokens = [\"\"]")
+        );
+        assert!(parse_application("topology = \"moebius\"").is_err());
+        assert!(parse_application("board-widht = 30").is_err());
+    }
+}
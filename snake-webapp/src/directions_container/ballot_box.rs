@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::model::direction_request::DirectionRequest;
+use crate::voter_identity::VoterId;
+
+use super::directions_array::{ContainDirections, DirectionsArray};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VoteOutcome {
+    Recorded,
+    Replaced,
+}
+
+// Votes of one tick in the order voters first cast them; a voter changing
+// their mind keeps their place.
+#[derive(Default)]
+pub struct BallotBox {
+    votes: DirectionsArray,
+    voters: HashMap<VoterId, usize>,
+}
+
+impl BallotBox {
+    pub fn cast(&mut self, voter: VoterId, direction: DirectionRequest) -> VoteOutcome {
+        match self.voters.get(&voter) {
+            Some(&index) => {
+                self.votes[index] = direction;
+                VoteOutcome::Replaced
+            }
+            None => {
+                self.voters.insert(voter, self.votes.len());
+                self.votes.push(direction);
+                VoteOutcome::Recorded
+            }
+        }
+    }
+
+    pub fn directions(&self) -> &DirectionsArray {
+        &self.votes
+    }
+
+    pub fn into_directions(self) -> DirectionsArray {
+        self.votes
+    }
+
+    pub fn voter_count(&self) -> usize {
+        self.voters.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.votes.reset_all_directions();
+        self.voters.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::direction_request::DirectionRequest;
+    use crate::voter_identity::VoterId;
+
+    use super::{BallotBox, VoteOutcome};
+
+    #[test]
+    fn test_should_count_only_latest_vote_when_voter_votes_twice() {
+        let mut ballot_box = BallotBox::default();
+        let alice = VoterId::new("cookie", "alice");
+        let bob = VoterId::new("cookie", "bob");
+        assert_eq!(
+            VoteOutcome::Recorded,
+            ballot_box.cast(alice.clone(), DirectionRequest::Up)
+        );
+        assert_eq!(
+            VoteOutcome::Recorded,
+            ballot_box.cast(bob.clone(), DirectionRequest::Left)
+        );
+        assert_eq!(
+            VoteOutcome::Replaced,
+            ballot_box.cast(alice.clone(), DirectionRequest::Down)
+        );
+        assert_eq!(
+            &vec![DirectionRequest::Down, DirectionRequest::Left],
+            ballot_box.directions()
+        );
+
+        ballot_box.clear();
+        assert_eq!(
+            VoteOutcome::Recorded,
+            ballot_box.cast(alice, DirectionRequest::Up)
+        );
+        assert_eq!(&vec![DirectionRequest::Up], ballot_box.directions());
+    }
+}
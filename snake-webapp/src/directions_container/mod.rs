@@ -1,20 +1,26 @@
+pub mod ballot_box;
 pub mod directions_array;
 
 use std::sync::Arc;
 
 use tokio::sync::mpsc::{channel, Sender};
-use tokio::sync::RwLock;
+use tokio::sync::{oneshot, RwLock};
 use tokio::task::JoinHandle;
 
 use crate::model::direction_request::DirectionRequest;
+use crate::model::vote_tally::VoteTallyResponse;
+use crate::voter_identity::VoterId;
+
+use self::ballot_box::{BallotBox, VoteOutcome};
+use self::directions_array::DirectionsArray;
+
+type VoteEnvelope = (VoterId, DirectionRequest, oneshot::Sender<VoteOutcome>);
 
-use self::directions_array::{
-    ContainDirections,
-    DirectionsArray
-};
 pub struct DirectionsContainer {
-    directions: Arc<RwLock<DirectionsArray>>,
-    direction_sender: Sender<DirectionRequest>,
+    directions: Arc<RwLock<BallotBox>>,
+    // Keeps the votes of the running tick secret until the tick closes.
+    blind: bool,
+    direction_sender: Sender<VoteEnvelope>,
     join_handle: JoinHandle<()>,
 }
 
@@ -22,43 +28,68 @@ pub struct DirectionsContainer {
 pub struct DirectionRegisterError;
 
 impl DirectionsContainer {
-    pub fn init_container() -> Self {
-        let directions = Arc::new(RwLock::new(Vec::<DirectionRequest>::new()));
-        let (direction_sender, mut direction_recv) = channel::<DirectionRequest>(1024);
+    pub fn init_container(channel_capacity: usize, blind: bool) -> Self {
+        let directions = Arc::new(RwLock::new(BallotBox::default()));
+        let (direction_sender, mut direction_recv) = channel::<VoteEnvelope>(channel_capacity);
         let arr = directions.clone();
         let join_handle = actix_web::rt::spawn(async move {
             loop {
                 let value = direction_recv.recv().await;
-                if let Some(direction) = value {
-                    let directions = &mut arr.write().await;
-                    directions.push(direction)
+                if let Some((voter, direction, reply)) = value {
+                    let outcome = arr.write().await.cast(voter, direction);
+                    let _ = reply.send(outcome);
                 }
             }
         });
         Self {
             directions,
+            blind,
             direction_sender,
             join_handle,
         }
     }
 
+    // Resolves once the vote is counted, telling whether it replaced the voter's earlier one.
     pub async fn register_direction_request(
         &self,
+        voter: VoterId,
         direction: DirectionRequest,
-    ) -> Result<(), DirectionRegisterError> {
+    ) -> Result<VoteOutcome, DirectionRegisterError> {
+        let (reply, outcome) = oneshot::channel();
         self.direction_sender
-            .send(direction)
+            .send((voter, direction, reply))
             .await
-            .map(|_| ())
-            .map_err(|_| DirectionRegisterError)
+            .map_err(|_| DirectionRegisterError)?;
+        outcome.await.map_err(|_| DirectionRegisterError)
     }
 
     pub async fn get_directions(&self) -> DirectionsArray {
-        self.directions.read().await.clone()
+        self.directions.read().await.directions().clone()
+    }
+
+    // Empties the box under a single lock, so every vote counts in exactly one tick.
+    pub async fn take_directions(&self) -> DirectionsArray {
+        std::mem::take(&mut *self.directions.write().await).into_directions()
+    }
+
+    pub fn is_blind(&self) -> bool {
+        self.blind
+    }
+
+    pub async fn visible_tally(&self) -> Option<VoteTallyResponse> {
+        if self.blind {
+            return None;
+        }
+        let directions = self.get_directions().await;
+        Some(VoteTallyResponse::from(directions.as_slice()))
+    }
+
+    pub async fn voter_count(&self) -> usize {
+        self.directions.read().await.voter_count()
     }
 
     pub async fn clear_directions(&self) {
-        self.directions.write().await.reset_all_directions()
+        self.directions.write().await.clear()
     }
 }
 
@@ -67,3 +98,33 @@ impl Drop for DirectionsContainer {
         self.join_handle.abort();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::model::direction_request::DirectionRequest;
+    use crate::voter_identity::VoterId;
+
+    use super::DirectionsContainer;
+
+    #[actix_web::test]
+    async fn test_should_hand_out_votes_once_when_directions_are_taken() {
+        let container = DirectionsContainer::init_container(16, false);
+        for (voter, direction) in [
+            ("alice", DirectionRequest::Up),
+            ("bob", DirectionRequest::Left),
+        ] {
+            container
+                .register_direction_request(VoterId::new("token", voter), direction)
+                .await
+                .unwrap();
+        }
+
+        let directions = container.take_directions().await;
+        assert_eq!(
+            vec![DirectionRequest::Up, DirectionRequest::Left],
+            directions
+        );
+        assert_eq!(0, container.voter_count().await);
+        assert!(container.take_directions().await.is_empty());
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use actix_web::web::Bytes;
+use futures_util::stream::{unfold, Stream};
+use serde::Serialize;
+use tokio::sync::broadcast::{channel, Receiver, Sender};
+use tokio::time::{interval_at, Instant, Interval, MissedTickBehavior};
+
+const EVENT_LOG_CAPACITY: usize = 256;
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(5);
+const KEEP_ALIVE_FRAME: &[u8] = b": keep-alive\n\n";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamEvent {
+    pub id: u64,
+    pub frame: Bytes,
+}
+
+struct EventLogState {
+    next_id: u64,
+    events: VecDeque<Arc<StreamEvent>>,
+}
+
+pub struct EventLog {
+    state: Mutex<EventLogState>,
+    sender: Sender<Arc<StreamEvent>>,
+    capacity: usize,
+}
+
+impl EventLog {
+    pub fn new() -> Self {
+        Self::with_capacity(EVENT_LOG_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_first_id(capacity, boot_epoch_id())
+    }
+
+    pub fn with_first_id(capacity: usize, first_id: u64) -> Self {
+        let (sender, _) = channel(capacity.max(1));
+        Self {
+            state: Mutex::new(EventLogState {
+                next_id: first_id.max(1),
+                events: VecDeque::with_capacity(capacity),
+            }),
+            sender,
+            capacity,
+        }
+    }
+
+    pub fn publish<T: Serialize>(&self, kind: &str, data: &T) {
+        let data = match serde_json::to_string(data) {
+            Ok(data) => data,
+            Err(_) => return,
+        };
+        let mut state = self.state.lock().unwrap();
+        let event = Arc::new(StreamEvent {
+            id: state.next_id,
+            frame: Bytes::from(format!(
+                "id: {}\nevent: {}\ndata: {}\n\n",
+                state.next_id, kind, data
+            )),
+        });
+        state.next_id += 1;
+        if state.events.len() == self.capacity {
+            state.events.pop_front();
+        }
+        state.events.push_back(event.clone());
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+
+    // Taken under the same lock as `publish`, so the backlog and the live
+    // receiver neither overlap nor leave a gap.
+    pub fn subscribe(
+        &self,
+        last_event_id: Option<u64>,
+    ) -> (VecDeque<Arc<StreamEvent>>, Receiver<Arc<StreamEvent>>) {
+        let state = self.state.lock().unwrap();
+        let backlog = match last_event_id {
+            Some(last_event_id) => state
+                .events
+                .iter()
+                .filter(|event| event.id > last_event_id)
+                .cloned()
+                .collect(),
+            None => VecDeque::new(),
+        };
+        (backlog, self.sender.subscribe())
+    }
+}
+
+// Ids start at the boot time in microseconds, so a browser resuming with a
+// `Last-Event-ID` from before a restart still sees the new events as newer.
+fn boot_epoch_id() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_micros() as u64)
+        .unwrap_or(0)
+}
+
+struct EventStreamState {
+    backlog: VecDeque<Arc<StreamEvent>>,
+    receiver: Receiver<Arc<StreamEvent>>,
+    keep_alive: Interval,
+}
+
+// A subscriber that lags behind the channel is disconnected; the browser
+// reconnects with `Last-Event-ID` and catches up from the event log.
+pub fn event_stream(
+    event_log: &EventLog,
+    last_event_id: Option<u64>,
+) -> impl Stream<Item = Result<Bytes, Infallible>> {
+    let (backlog, receiver) = event_log.subscribe(last_event_id);
+    let mut keep_alive = interval_at(Instant::now() + KEEP_ALIVE_INTERVAL, KEEP_ALIVE_INTERVAL);
+    keep_alive.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let state = EventStreamState {
+        backlog,
+        receiver,
+        keep_alive,
+    };
+
+    unfold(state, |mut state| async move {
+        if let Some(event) = state.backlog.pop_front() {
+            return Some((Ok(event.frame.clone()), state));
+        }
+        tokio::select! {
+            biased;
+            event = state.receiver.recv() => match event {
+                Ok(event) => {
+                    state.keep_alive.reset();
+                    Some((Ok(event.frame.clone()), state))
+                }
+                Err(_) => None,
+            },
+            _ = state.keep_alive.tick() => Some((Ok(Bytes::from_static(KEEP_ALIVE_FRAME)), state)),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+
+    use super::{event_stream, EventLog};
+
+    #[test]
+    fn test_should_format_events_with_id_type_and_json_data() {
+        let event_log = EventLog::with_first_id(8, 1);
+        event_log.publish("game_started", &serde_json::json!({ "game_id": 2 }));
+        let (backlog, _) = event_log.subscribe(Some(0));
+        assert_eq!(
+            "id: 1\nevent: game_started\ndata: {\"game_id\":2}\n\n",
+            std::str::from_utf8(&backlog[0].frame).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_should_resume_after_last_event_id_from_bounded_log() {
+        let event_log = EventLog::with_first_id(3, 1);
+        for tick in 0..5 {
+            event_log.publish("tick", &tick);
+        }
+        let (backlog, _) = event_log.subscribe(Some(3));
+        assert_eq!(
+            vec![4, 5],
+            backlog.iter().map(|event| event.id).collect::<Vec<_>>()
+        );
+        let (backlog, _) = event_log.subscribe(Some(0));
+        assert_eq!(
+            vec![3, 4, 5],
+            backlog.iter().map(|event| event.id).collect::<Vec<_>>()
+        );
+        let (backlog, _) = event_log.subscribe(None);
+        assert!(backlog.is_empty());
+    }
+
+    #[test]
+    fn test_should_keep_ids_increasing_when_log_is_recreated() {
+        let before_restart = EventLog::new();
+        before_restart.publish("tick", &1);
+        let (backlog, _) = before_restart.subscribe(Some(0));
+        let last_event_id = backlog[0].id;
+
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        let after_restart = EventLog::new();
+        after_restart.publish("tick", &2);
+        let (backlog, _) = after_restart.subscribe(Some(last_event_id));
+        assert_eq!(1, backlog.len());
+        assert!(backlog[0].id > last_event_id);
+    }
+
+    #[actix_web::test]
+    async fn test_should_stream_backlog_then_live_events() {
+        let event_log = EventLog::with_first_id(8, 1);
+        event_log.publish("tick", &1);
+        let stream = event_stream(&event_log, Some(0));
+        event_log.publish("tick", &2);
+
+        let frames = stream
+            .take(2)
+            .map(|frame| frame.unwrap())
+            .collect::<Vec<_>>()
+            .await;
+        assert!(frames[0].starts_with(b"id: 1\n"));
+        assert!(frames[1].starts_with(b"id: 2\n"));
+    }
+}
@@ -0,0 +1,55 @@
+use actix_web::http::header::ContentType;
+use actix_web::{get, HttpResponse};
+
+// Embedded so the server still ships as a single binary.
+const INDEX_HTML: &str = include_str!("../../static/index.html");
+const APP_JS: &str = include_str!("../../static/app.js");
+const STYLE_CSS: &str = include_str!("../../static/style.css");
+
+#[get("/")]
+async fn index() -> HttpResponse {
+    HttpResponse::Ok()
+        .insert_header(ContentType::html())
+        .body(INDEX_HTML)
+}
+
+#[get("/app.js")]
+async fn app_js() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/javascript; charset=utf-8")
+        .body(APP_JS)
+}
+
+#[get("/style.css")]
+async fn style_css() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/css; charset=utf-8")
+        .body(STYLE_CSS)
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{test, App};
+
+    use super::{app_js, index, style_css};
+
+    #[actix_web::test]
+    async fn test_should_serve_embedded_assets_when_requested() {
+        let app =
+            test::init_service(App::new().service(index).service(app_js).service(style_css)).await;
+
+        for (path, content_type) in [
+            ("/", "text/html; charset=utf-8"),
+            ("/app.js", "text/javascript; charset=utf-8"),
+            ("/style.css", "text/css; charset=utf-8"),
+        ] {
+            let response =
+                test::call_service(&app, test::TestRequest::get().uri(path).to_request()).await;
+            assert!(response.status().is_success());
+            assert_eq!(
+                content_type,
+                response.headers().get("content-type").unwrap()
+            );
+        }
+    }
+}
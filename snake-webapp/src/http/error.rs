@@ -30,3 +30,133 @@ impl ResponseError for NotFoundError {
         StatusCode::NOT_FOUND
     }
 }
+
+#[derive(Debug, Clone, Copy)]
+pub struct BadRequestError(&'static str);
+
+impl BadRequestError {
+    pub fn new(message: &'static str) -> Self {
+        Self(message)
+    }
+}
+
+impl fmt::Display for BadRequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl ResponseError for BadRequestError {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .insert_header(ContentType::html())
+            .body(self.to_string())
+    }
+
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct UnauthorizedError;
+
+impl fmt::Display for UnauthorizedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "A valid admin token is required")
+    }
+}
+
+impl ResponseError for UnauthorizedError {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .insert_header(ContentType::html())
+            .insert_header(("WWW-Authenticate", "Bearer"))
+            .body(self.to_string())
+    }
+
+    fn status_code(&self) -> StatusCode {
+        StatusCode::UNAUTHORIZED
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ForbiddenError(&'static str);
+
+impl ForbiddenError {
+    pub fn new(message: &'static str) -> Self {
+        Self(message)
+    }
+}
+
+impl fmt::Display for ForbiddenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl ResponseError for ForbiddenError {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .insert_header(ContentType::html())
+            .body(self.to_string())
+    }
+
+    fn status_code(&self) -> StatusCode {
+        StatusCode::FORBIDDEN
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ServiceUnavailableError(&'static str);
+
+impl ServiceUnavailableError {
+    pub fn new(message: &'static str) -> Self {
+        Self(message)
+    }
+}
+
+impl fmt::Display for ServiceUnavailableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl ResponseError for ServiceUnavailableError {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .insert_header(ContentType::html())
+            .body(self.to_string())
+    }
+
+    fn status_code(&self) -> StatusCode {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TooManyRequestsError(&'static str);
+
+impl TooManyRequestsError {
+    pub fn new(message: &'static str) -> Self {
+        Self(message)
+    }
+}
+
+impl fmt::Display for TooManyRequestsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl ResponseError for TooManyRequestsError {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .insert_header(ContentType::html())
+            .body(self.to_string())
+    }
+
+    fn status_code(&self) -> StatusCode {
+        StatusCode::TOO_MANY_REQUESTS
+    }
+}
@@ -0,0 +1,160 @@
+use actix_web::web::Data;
+use actix_ws::{Message, MessageStream, Session};
+use bytestring::ByteString;
+use tokio::sync::broadcast::{channel, error::RecvError, Receiver, Sender};
+
+use crate::directions_container::DirectionsContainer;
+use crate::model::live_message::LiveMessage;
+use crate::snake_context_wrapper::SnakeWebAppContext;
+
+const LIVE_UPDATES_CAPACITY: usize = 64;
+
+pub struct LiveUpdates {
+    sender: Sender<ByteString>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Delivery {
+    Send(ByteString),
+    Resync,
+    Closed,
+}
+
+impl LiveUpdates {
+    pub fn new() -> Self {
+        let (sender, _) = channel(LIVE_UPDATES_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> Receiver<ByteString> {
+        self.sender.subscribe()
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+
+    // Serialized once here and shared by every subscriber.
+    pub fn publish(&self, message: &LiveMessage) {
+        if let Ok(json) = serde_json::to_string(message) {
+            let _ = self.sender.send(json.into());
+        }
+    }
+}
+
+// A subscriber that fell behind the channel capacity skips the backlog and
+// gets a fresh snapshot instead.
+pub async fn next_delivery(receiver: &mut Receiver<ByteString>) -> Delivery {
+    match receiver.recv().await {
+        Ok(message) => Delivery::Send(message),
+        Err(RecvError::Lagged(_)) => {
+            *receiver = receiver.resubscribe();
+            Delivery::Resync
+        }
+        Err(RecvError::Closed) => Delivery::Closed,
+    }
+}
+
+pub async fn snapshot_message(
+    snake_ctx: &SnakeWebAppContext,
+    directions_container: &DirectionsContainer,
+) -> ByteString {
+    let message = LiveMessage::Snapshot {
+        state: snake_ctx.get_game_state().await,
+        votes: directions_container.visible_tally().await,
+    };
+    serde_json::to_string(&message)
+        .expect("live message should serialize")
+        .into()
+}
+
+pub async fn serve_websocket(
+    mut session: Session,
+    mut incoming: MessageStream,
+    mut receiver: Receiver<ByteString>,
+    snake_ctx: Data<SnakeWebAppContext>,
+    directions_container: Data<DirectionsContainer>,
+) {
+    let snapshot = snapshot_message(&snake_ctx, &directions_container).await;
+    if session.text(snapshot).await.is_err() {
+        return;
+    }
+
+    loop {
+        tokio::select! {
+            delivery = next_delivery(&mut receiver) => {
+                let message = match delivery {
+                    Delivery::Send(message) => message,
+                    Delivery::Resync => snapshot_message(&snake_ctx, &directions_container).await,
+                    Delivery::Closed => break,
+                };
+                if session.text(message).await.is_err() {
+                    return;
+                }
+            }
+            message = incoming.recv() => match message {
+                Some(Ok(Message::Ping(bytes))) => {
+                    if session.pong(&bytes).await.is_err() {
+                        return;
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => (),
+            }
+        }
+    }
+    let _ = session.close(None).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{next_delivery, Delivery, LiveUpdates};
+    use crate::model::direction_request::DirectionRequest;
+    use crate::model::game_state::GameStateResponse;
+    use crate::model::live_message::LiveMessage;
+    use crate::model::vote_tally::VoteTallyResponse;
+
+    use snake_core::SnakeContext;
+
+    fn tick_message() -> LiveMessage {
+        let mut game = SnakeContext::new();
+        game.new_game();
+        LiveMessage::Tick {
+            state: GameStateResponse::new(&game.snapshot(), game.stats(), game.game_id(), None),
+            votes: VoteTallyResponse::from([DirectionRequest::Up, DirectionRequest::Up].as_slice()),
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_should_deliver_published_tick_to_every_subscriber() {
+        let live_updates = LiveUpdates::new();
+        let mut first = live_updates.subscribe();
+        let mut second = live_updates.subscribe();
+        live_updates.publish(&tick_message());
+
+        for receiver in [&mut first, &mut second] {
+            match next_delivery(receiver).await {
+                Delivery::Send(message) => {
+                    let json: serde_json::Value = serde_json::from_str(&message).unwrap();
+                    assert_eq!("tick", json["type"]);
+                    assert_eq!(2, json["votes"]["up"]);
+                }
+                other => panic!("unexpected delivery {:?}", other),
+            }
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_should_resync_subscriber_when_it_lags_behind() {
+        let live_updates = LiveUpdates::new();
+        let mut slow = live_updates.subscribe();
+        for _ in 0..super::LIVE_UPDATES_CAPACITY + 1 {
+            live_updates.publish(&tick_message());
+        }
+
+        assert_eq!(Delivery::Resync, next_delivery(&mut slow).await);
+        live_updates.publish(&tick_message());
+        assert!(matches!(next_delivery(&mut slow).await, Delivery::Send(_)));
+        assert!(slow.is_empty());
+    }
+}
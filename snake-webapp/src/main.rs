@@ -1,42 +1,119 @@
+mod admin_api;
+mod app_settings;
 mod directions_container;
+mod event_stream;
+mod frontend;
 mod http;
+mod live_updates;
 mod model;
 mod next_move_scheduler;
+mod rooms;
+mod rooms_api;
 mod snake_context_wrapper;
+mod vote_resolver;
+mod voter_identity;
 mod web_api;
 
-use actix_settings::{ApplySettings, Settings};
+use std::time::Duration;
+
+use actix_settings::ApplySettings;
+use actix_web::cookie::Key;
 use actix_web::middleware::Logger;
-use actix_web::web::Data;
+use actix_web::web::{scope, Data};
 use actix_web::{App, HttpServer};
 
 use crate::{
-    directions_container::DirectionsContainer,
-    next_move_scheduler::NextMoveScheduler,
-    snake_context_wrapper::SnakeWebAppContext,
+    admin_api::{admin_auth::AdminToken, audit_log::AuditLog},
+    app_settings::WebAppSettings,
+    rooms::{run_room_collector, Room, RoomRegistry},
+    voter_identity::VoterIdentity,
 };
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    let settings = Settings::parse_toml("./Configuration.toml")
+    let mut settings = WebAppSettings::parse_toml("./Configuration.toml")
         .expect("Configuration.toml loading error occured");
+    if let Ok(admin_token) = std::env::var("SNAKE_ADMIN_TOKEN") {
+        settings.application.admin_token = Some(admin_token);
+    }
+    let app_settings = &settings.application;
+    if let Err(error) = app_settings.validate() {
+        eprintln!("Invalid [application] settings in Configuration.toml: {}", error);
+        std::process::exit(1);
+    }
 
-    let directions_container = Data::new(DirectionsContainer::init_container());
-    let snake_ctx = Data::new(SnakeWebAppContext::new());
-    snake_ctx.new_game().await;
-
-    let _scheduler =
-        NextMoveScheduler::run_scheduler(snake_ctx.clone(), directions_container.clone());
+    // The top-level /snake routes play in a room that is never collected.
+    let default_room = Room::start(app_settings).await;
+    let snake_ctx = default_room.snake_ctx.clone();
+    let directions_container = default_room.directions_container.clone();
+    let live_updates = default_room.live_updates.clone();
+    let event_log = default_room.event_log.clone();
+    let scheduler_handle = Data::new(default_room.scheduler.handle());
+    let admin_token = Data::new(AdminToken(app_settings.admin_token.clone()));
+    let audit_log = Data::new(AuditLog::new());
+    // Voter cookies are signed with a per-process key; after a restart old
+    // cookies are ignored and voters are counted by address again.
+    let voter_identity = Data::new(VoterIdentity {
+        header: app_settings.voter_id_header.clone(),
+        tokens: app_settings.voter_tokens.iter().cloned().collect(),
+        key: Key::generate(),
+    });
+    let room_defaults = Data::new(app_settings.clone());
+    let room_registry = Data::new(RoomRegistry::new(
+        app_settings.max_rooms,
+        Duration::from_secs(app_settings.room_idle_timeout_seconds),
+        Duration::from_secs(app_settings.room_creation_cooldown_seconds),
+    ));
+    let room_collector = run_room_collector(room_registry.clone());
 
-    HttpServer::new(move || {
+    let result = HttpServer::new(move || {
         App::new()
             .wrap(Logger::default())
             .app_data(snake_ctx.clone())
             .app_data(directions_container.clone())
+            .app_data(live_updates.clone())
+            .app_data(event_log.clone())
+            .app_data(admin_token.clone())
+            .app_data(audit_log.clone())
+            .app_data(scheduler_handle.clone())
+            .app_data(voter_identity.clone())
+            .app_data(room_defaults.clone())
+            .app_data(room_registry.clone())
+            .service(frontend::index)
+            .service(frontend::app_js)
+            .service(frontend::style_css)
             .service(web_api::show_plane)
+            .service(web_api::show_state)
+            .service(web_api::show_votes)
+            .service(web_api::live_board)
+            .service(web_api::game_events)
             .service(web_api::request_direction)
+            .service(rooms_api::create_room)
+            .service(rooms_api::list_rooms)
+            .service(rooms_api::delete_room)
+            .service(rooms_api::show_room_plane)
+            .service(rooms_api::show_room_state)
+            .service(rooms_api::show_room_votes)
+            .service(rooms_api::room_events)
+            .service(rooms_api::request_room_direction)
+            .service(rooms_api::room_live_board)
+            .service(
+                scope("/admin")
+                    .service(admin_api::pause)
+                    .service(admin_api::resume)
+                    .service(admin_api::step)
+                    .service(admin_api::reset)
+                    .service(admin_api::set_tick_interval)
+                    .service(admin_api::clear_tick_interval)
+                    .service(admin_api::force_direction)
+                    .service(admin_api::show_audit_log),
+            )
     })
     .apply_settings(&settings)
     .run()
-    .await
+    .await;
+
+    room_collector.abort();
+    drop(default_room);
+    result
 }
@@ -1,8 +1,11 @@
 use std::fmt;
 
+use serde::Serialize;
+
 use snake_core::Direction;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum DirectionRequest {
     Up,
     Down,
@@ -44,9 +47,9 @@ impl From<Direction> for DirectionRequest {
     }
 }
 
-impl Into<Direction> for DirectionRequest {
-    fn into(self) -> snake_core::Direction {
-        match self {
+impl From<DirectionRequest> for Direction {
+    fn from(direction: DirectionRequest) -> Self {
+        match direction {
             DirectionRequest::Up => snake_core::Direction::Up,
             DirectionRequest::Down => snake_core::Direction::Down,
             DirectionRequest::Left => snake_core::Direction::Left,
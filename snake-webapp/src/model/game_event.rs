@@ -0,0 +1,49 @@
+use serde::Serialize;
+
+use snake_core::game_mode::{DeathCause, GameOverReason, GameResult};
+
+use crate::model::vote_tally::VoteTallyResponse;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct VoteTallyEventResponse {
+    pub tick: u64,
+    pub votes: VoteTallyResponse,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct GameStartedResponse {
+    pub game_id: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct GameOverResponse {
+    pub game_id: u64,
+    pub reason: &'static str,
+    pub score: u32,
+    pub final_length: usize,
+    pub ticks: u64,
+}
+
+impl GameOverResponse {
+    pub fn new(game_id: u64, result: &GameResult) -> Self {
+        Self {
+            game_id,
+            reason: reason_name(result.reason),
+            score: result.score,
+            final_length: result.final_length,
+            ticks: result.stats.ticks,
+        }
+    }
+}
+
+fn reason_name(reason: GameOverReason) -> &'static str {
+    match reason {
+        GameOverReason::Died(DeathCause::SelfCollision) => "self_collision",
+        GameOverReason::Died(DeathCause::WallCollision) => "wall_collision",
+        GameOverReason::Died(DeathCause::CaughtByArena) => "caught_by_arena",
+        GameOverReason::Died(DeathCause::CreatureContact) => "creature_contact",
+        GameOverReason::Died(DeathCause::SnakeCollision) => "snake_collision",
+        GameOverReason::TimeUp => "time_up",
+        GameOverReason::TargetReached => "target_reached",
+    }
+}
@@ -0,0 +1,138 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use snake_core::game_snapshot::GameSnapshot;
+use snake_core::{GameStats, Point};
+
+use crate::model::direction_request::DirectionRequest;
+
+// Bumped only on breaking changes; new optional fields keep the version.
+pub const GAME_STATE_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PointResponse {
+    pub x: u32,
+    pub y: u32,
+}
+
+impl From<Point> for PointResponse {
+    fn from(point: Point) -> Self {
+        Self {
+            x: point.0,
+            y: point.1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct BoardResponse {
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct StatsResponse {
+    pub score: u32,
+    pub ticks: u64,
+    pub apples_eaten: u32,
+    pub deaths: u32,
+    pub longest_length: usize,
+    pub lives_left: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GameStateResponse {
+    pub schema_version: u32,
+    pub game_id: u64,
+    pub tick: u64,
+    pub board: BoardResponse,
+    pub snake: Vec<PointResponse>,
+    pub head: Option<PointResponse>,
+    pub direction: DirectionRequest,
+    pub apples: Vec<PointResponse>,
+    pub walls: Vec<PointResponse>,
+    pub creatures: Vec<PointResponse>,
+    pub remembered_apples: Vec<PointResponse>,
+    pub remembered_walls: Vec<PointResponse>,
+    pub stats: StatsResponse,
+    pub next_tick_at_unix_ms: Option<u64>,
+}
+
+impl GameStateResponse {
+    pub fn new(
+        snapshot: &GameSnapshot,
+        stats: GameStats,
+        game_id: u64,
+        next_tick_at: Option<SystemTime>,
+    ) -> Self {
+        Self {
+            schema_version: GAME_STATE_SCHEMA_VERSION,
+            game_id,
+            tick: snapshot.tick,
+            board: BoardResponse {
+                width: snapshot.width,
+                height: snapshot.height,
+            },
+            snake: points(&snapshot.snake),
+            head: snapshot.head().map(PointResponse::from),
+            direction: snapshot.direction.into(),
+            apples: points(&snapshot.apples),
+            walls: points(&snapshot.walls),
+            creatures: points(&snapshot.creatures),
+            remembered_apples: points(&snapshot.remembered_apples),
+            remembered_walls: points(&snapshot.remembered_walls),
+            stats: StatsResponse {
+                score: snapshot.score,
+                ticks: stats.ticks,
+                apples_eaten: stats.apples_eaten,
+                deaths: stats.deaths,
+                longest_length: stats.longest_length,
+                lives_left: snapshot.lives_left,
+            },
+            next_tick_at_unix_ms: next_tick_at.and_then(unix_milliseconds),
+        }
+    }
+}
+
+fn points(points: &[Point]) -> Vec<PointResponse> {
+    points.iter().map(|&point| point.into()).collect()
+}
+
+fn unix_milliseconds(time: SystemTime) -> Option<u64> {
+    time.duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|duration| duration.as_millis() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use snake_core::SnakeContext;
+
+    use super::GameStateResponse;
+
+    #[test]
+    fn test_should_serialize_game_state_with_versioned_schema() {
+        let mut game = SnakeContext::new();
+        game.new_game();
+        let next_tick_at = UNIX_EPOCH + Duration::from_millis(1_500);
+        let state = GameStateResponse::new(
+            &game.snapshot(),
+            game.stats(),
+            game.game_id(),
+            Some(next_tick_at),
+        );
+
+        let json = serde_json::to_value(&state).expect("state should serialize");
+        assert_eq!(1, json["schema_version"]);
+        assert_eq!(1, json["game_id"]);
+        assert_eq!(20, json["board"]["width"]);
+        assert_eq!(json["snake"][0], json["head"]);
+        assert_eq!(serde_json::json!({ "x": 10, "y": 5 }), json["head"]);
+        assert_eq!("left", json["direction"]);
+        assert_eq!(3, json["stats"]["longest_length"]);
+        assert_eq!(1_500, json["next_tick_at_unix_ms"]);
+    }
+}
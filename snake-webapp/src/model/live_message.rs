@@ -0,0 +1,18 @@
+use serde::Serialize;
+
+use crate::model::game_state::GameStateResponse;
+use crate::model::vote_tally::VoteTallyResponse;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LiveMessage {
+    // `votes` is null while blind voting hides the running tick.
+    Snapshot {
+        state: GameStateResponse,
+        votes: Option<VoteTallyResponse>,
+    },
+    Tick {
+        state: GameStateResponse,
+        votes: VoteTallyResponse,
+    },
+}
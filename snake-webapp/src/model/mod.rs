@@ -1 +1,7 @@
-pub mod direction_request;
\ No newline at end of file
+pub mod direction_request;
+pub mod game_event;
+pub mod game_state;
+pub mod live_message;
+pub mod room;
+pub mod vote;
+pub mod vote_tally;
\ No newline at end of file
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+use crate::app_settings::{AppSettings, BoardTopology, VoteStrategy};
+use crate::model::game_state::BoardResponse;
+
+// Every field is optional; a missing one keeps the server's [application] value.
+// The keys are spelled as in Configuration.toml.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub struct RoomConfigRequest {
+    pub tick_interval_milliseconds: Option<u64>,
+    pub board_width: Option<u32>,
+    pub board_height: Option<u32>,
+    pub topology: Option<BoardTopology>,
+    pub apple_spawn_one_in: Option<u32>,
+    pub vote_strategy: Option<VoteStrategy>,
+    pub blind_votes: Option<bool>,
+}
+
+impl RoomConfigRequest {
+    pub fn apply(&self, defaults: &AppSettings) -> AppSettings {
+        AppSettings {
+            tick_interval_milliseconds: self
+                .tick_interval_milliseconds
+                .unwrap_or(defaults.tick_interval_milliseconds),
+            board_width: self.board_width.unwrap_or(defaults.board_width),
+            board_height: self.board_height.unwrap_or(defaults.board_height),
+            topology: self.topology.unwrap_or(defaults.topology),
+            apple_spawn_one_in: self
+                .apple_spawn_one_in
+                .unwrap_or(defaults.apple_spawn_one_in),
+            vote_strategy: self.vote_strategy.unwrap_or(defaults.vote_strategy),
+            blind_votes: self.blind_votes.unwrap_or(defaults.blind_votes),
+            ..defaults.clone()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RoomResponse {
+    pub id: String,
+    pub game_id: u64,
+    pub tick: u64,
+    pub board: BoardResponse,
+    pub score: u32,
+    pub connected_clients: usize,
+    pub idle_seconds: u64,
+    pub created_at_unix_ms: u64,
+}
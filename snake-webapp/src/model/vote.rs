@@ -0,0 +1,21 @@
+use serde::Serialize;
+
+use crate::directions_container::ballot_box::VoteOutcome;
+use crate::model::direction_request::DirectionRequest;
+use crate::model::vote_tally::VoteTallyResponse;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct VoteResponse {
+    pub direction: DirectionRequest,
+    pub outcome: VoteOutcome,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct VoteStatusResponse {
+    pub tick: u64,
+    // `None` while blind voting hides the running tick.
+    pub votes: Option<VoteTallyResponse>,
+    pub voters: usize,
+    pub blind: bool,
+    pub milliseconds_until_tick: Option<u64>,
+}
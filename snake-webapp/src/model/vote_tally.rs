@@ -0,0 +1,26 @@
+use serde::Serialize;
+
+use crate::model::direction_request::DirectionRequest;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct VoteTallyResponse {
+    pub up: usize,
+    pub down: usize,
+    pub left: usize,
+    pub right: usize,
+}
+
+impl From<&[DirectionRequest]> for VoteTallyResponse {
+    fn from(votes: &[DirectionRequest]) -> Self {
+        let mut tally = Self::default();
+        for vote in votes {
+            match vote {
+                DirectionRequest::Up => tally.up += 1,
+                DirectionRequest::Down => tally.down += 1,
+                DirectionRequest::Left => tally.left += 1,
+                DirectionRequest::Right => tally.right += 1,
+            }
+        }
+        tally
+    }
+}
@@ -1,47 +1,227 @@
 mod determine_next_move;
 
-use std::time::Duration;
-
 use actix_web::{rt::spawn, web::Data};
 
-use tokio::{task::JoinHandle, time::interval};
+use serde::Serialize;
+use tokio::{
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+    time::{sleep_until, Instant},
+};
 
 use crate::{
     directions_container::DirectionsContainer,
-    snake_context_wrapper::SnakeWebAppContext
+    event_stream::EventLog,
+    live_updates::LiveUpdates,
+    model::{
+        direction_request::DirectionRequest,
+        game_event::{GameOverResponse, GameStartedResponse, VoteTallyEventResponse},
+        live_message::LiveMessage,
+        vote_tally::VoteTallyResponse,
+    },
+    snake_context_wrapper::SnakeWebAppContext,
+    vote_resolver::VoteResolver,
 };
 
 use self::determine_next_move::determine_next_move;
 
-const SCHEDULER_INTERVAL_MILLISECONDS: u64 = 10000;
+const SCHEDULER_COMMAND_CAPACITY: usize = 16;
+
+type CommandEnvelope = (SchedulerCommand, oneshot::Sender<SchedulerStatus>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedulerCommand {
+    Pause,
+    Resume,
+    Step,
+    Reset,
+    // `None` goes back to the pace of the game's speed policy.
+    SetTickInterval(Option<u64>),
+    ForceDirection(DirectionRequest),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SchedulerStatus {
+    pub paused: bool,
+    pub tick_interval_milliseconds: u64,
+    pub forced_direction: Option<DirectionRequest>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SchedulerStoppedError;
+
+#[derive(Clone)]
+pub struct SchedulerHandle {
+    sender: mpsc::Sender<CommandEnvelope>,
+}
+
+impl SchedulerHandle {
+    // Resolves once the scheduler has applied the command.
+    pub async fn send(
+        &self,
+        command: SchedulerCommand,
+    ) -> Result<SchedulerStatus, SchedulerStoppedError> {
+        let (reply, status) = oneshot::channel();
+        self.sender
+            .send((command, reply))
+            .await
+            .map_err(|_| SchedulerStoppedError)?;
+        status.await.map_err(|_| SchedulerStoppedError)
+    }
+}
 
 pub struct NextMoveScheduler {
     join_handle: JoinHandle<()>,
+    handle: SchedulerHandle,
+}
+
+struct SchedulerLoop {
+    snake_ctx: Data<SnakeWebAppContext>,
+    directions_container: Data<DirectionsContainer>,
+    live_updates: Data<LiveUpdates>,
+    event_log: Data<EventLog>,
+    vote_resolver: Box<dyn VoteResolver + Send>,
+    paused: bool,
+    forced_direction: Option<DirectionRequest>,
+    next_tick: Instant,
 }
 
 impl NextMoveScheduler {
     pub fn run_scheduler(
         snake_ctx: Data<SnakeWebAppContext>,
         directions_container: Data<DirectionsContainer>,
+        live_updates: Data<LiveUpdates>,
+        event_log: Data<EventLog>,
+        vote_resolver: Box<dyn VoteResolver + Send>,
     ) -> Self {
-        let mut interval = interval(Duration::from_millis(SCHEDULER_INTERVAL_MILLISECONDS));
-        interval.reset();
-
+        let (sender, mut commands) = mpsc::channel::<CommandEnvelope>(SCHEDULER_COMMAND_CAPACITY);
         let join_handle = spawn(async move {
+            let mut scheduler = SchedulerLoop {
+                snake_ctx,
+                directions_container,
+                live_updates,
+                event_log,
+                vote_resolver,
+                paused: false,
+                forced_direction: None,
+                next_tick: Instant::now(),
+            };
+            scheduler.publish_game_started().await;
+            scheduler.schedule_next_tick().await;
             loop {
-                interval.tick().await;
-                let directions = directions_container.get_directions().await;
-                let current_direction = snake_ctx.current_direction().await;
-                let next_move = determine_next_move(directions, current_direction);
-                if let Some(next_move) = next_move {
-                    snake_ctx.update_direction(next_move.into()).await;
+                tokio::select! {
+                    _ = sleep_until(scheduler.next_tick), if !scheduler.paused => {
+                        scheduler.tick().await;
+                    }
+                    Some((command, reply)) = commands.recv() => {
+                        let status = scheduler.apply(command).await;
+                        let _ = reply.send(status);
+                    }
                 }
-                directions_container.clear_directions().await;
-                snake_ctx.update_game_state().await;
             }
         });
 
-        Self { join_handle }
+        Self {
+            join_handle,
+            handle: SchedulerHandle { sender },
+        }
+    }
+
+    pub fn handle(&self) -> SchedulerHandle {
+        self.handle.clone()
+    }
+}
+
+impl SchedulerLoop {
+    async fn schedule_next_tick(&mut self) {
+        self.next_tick = Instant::now() + self.snake_ctx.schedule_next_tick().await;
+    }
+
+    async fn publish_game_started(&self) {
+        self.event_log.publish("game_started", &GameStartedResponse {
+            game_id: self.snake_ctx.game_id().await,
+        });
+    }
+
+    async fn tick(&mut self) {
+        let directions = self.directions_container.take_directions().await;
+        let votes = VoteTallyResponse::from(directions.as_slice());
+        let current_direction = self.snake_ctx.current_direction().await;
+        let next_move = match self.forced_direction.take() {
+            Some(forced_direction) => Some(forced_direction),
+            None => determine_next_move(self.vote_resolver.as_mut(), directions, current_direction),
+        };
+        if let Some(next_move) = next_move {
+            self.snake_ctx.update_direction(next_move).await;
+        }
+        let game_id = self.snake_ctx.game_id().await;
+        let tick_result = self.snake_ctx.update_game_state().await;
+
+        if !self.paused {
+            self.schedule_next_tick().await;
+        }
+        let state = self.snake_ctx.get_game_state().await;
+        self.event_log.publish("vote_tally", &VoteTallyEventResponse {
+            tick: tick_result.delta.from_tick,
+            votes,
+        });
+        if let Some(result) = tick_result.game_over {
+            self.event_log.publish("game_over", &GameOverResponse::new(game_id, &result));
+            self.event_log.publish("game_started", &GameStartedResponse {
+                game_id: state.game_id,
+            });
+        }
+        self.event_log.publish("tick", &state);
+        self.live_updates.publish(&LiveMessage::Tick { state, votes });
+    }
+
+    async fn apply(&mut self, command: SchedulerCommand) -> SchedulerStatus {
+        match command {
+            SchedulerCommand::Pause => {
+                self.paused = true;
+                self.snake_ctx.cancel_next_tick().await;
+                self.publish_snapshot().await;
+            }
+            SchedulerCommand::Resume if self.paused => {
+                self.paused = false;
+                self.schedule_next_tick().await;
+                self.publish_snapshot().await;
+            }
+            SchedulerCommand::Resume => (),
+            SchedulerCommand::Step => self.tick().await,
+            SchedulerCommand::Reset => {
+                self.snake_ctx.new_game().await;
+                self.directions_container.clear_directions().await;
+                self.forced_direction = None;
+                if !self.paused {
+                    self.schedule_next_tick().await;
+                }
+                self.publish_game_started().await;
+                self.publish_snapshot().await;
+            }
+            SchedulerCommand::SetTickInterval(milliseconds) => {
+                self.snake_ctx.set_tick_interval_milliseconds(milliseconds).await;
+                if !self.paused {
+                    self.schedule_next_tick().await;
+                    self.publish_snapshot().await;
+                }
+            }
+            SchedulerCommand::ForceDirection(direction) => self.forced_direction = Some(direction),
+        }
+
+        SchedulerStatus {
+            paused: self.paused,
+            tick_interval_milliseconds: self.snake_ctx.tick_interval_milliseconds().await,
+            forced_direction: self.forced_direction,
+        }
+    }
+
+    // Lets live clients pick up a state change that happened between ticks.
+    async fn publish_snapshot(&self) {
+        self.live_updates.publish(&LiveMessage::Snapshot {
+            state: self.snake_ctx.get_game_state().await,
+            votes: self.directions_container.visible_tally().await,
+        });
     }
 }
 
@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use actix_web::{rt::spawn, web::Data};
+use rand::Rng;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tokio::time::interval;
+
+use crate::app_settings::AppSettings;
+use crate::directions_container::DirectionsContainer;
+use crate::event_stream::EventLog;
+use crate::live_updates::LiveUpdates;
+use crate::next_move_scheduler::NextMoveScheduler;
+use crate::snake_context_wrapper::SnakeWebAppContext;
+
+const ROOM_COLLECTOR_INTERVAL: Duration = Duration::from_secs(30);
+
+// Everything one game needs, driven by its own scheduler task. Dropping the
+// room stops the scheduler and closes its live channels.
+pub struct Room {
+    pub snake_ctx: Data<SnakeWebAppContext>,
+    pub directions_container: Data<DirectionsContainer>,
+    pub live_updates: Data<LiveUpdates>,
+    pub event_log: Data<EventLog>,
+    pub scheduler: NextMoveScheduler,
+    pub created_at: SystemTime,
+    last_active: Mutex<Instant>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoomCreateError {
+    LimitReached,
+    CreatedTooRecently,
+}
+
+impl Room {
+    pub async fn start(settings: &AppSettings) -> Self {
+        let snake_ctx = Data::new(SnakeWebAppContext::new(settings.game_config()));
+        snake_ctx.new_game().await;
+        let directions_container = Data::new(DirectionsContainer::init_container(
+            settings.vote_channel_capacity,
+            settings.blind_votes,
+        ));
+        let live_updates = Data::new(LiveUpdates::new());
+        let event_log = Data::new(EventLog::new());
+        let scheduler = NextMoveScheduler::run_scheduler(
+            snake_ctx.clone(),
+            directions_container.clone(),
+            live_updates.clone(),
+            event_log.clone(),
+            settings.vote_resolver(),
+        );
+
+        Self {
+            snake_ctx,
+            directions_container,
+            live_updates,
+            event_log,
+            scheduler,
+            created_at: SystemTime::now(),
+            last_active: Mutex::new(Instant::now()),
+        }
+    }
+
+    pub fn touch(&self) {
+        *self.last_active.lock().unwrap() = Instant::now();
+    }
+
+    pub fn idle_for(&self) -> Duration {
+        self.last_active.lock().unwrap().elapsed()
+    }
+
+    pub fn created_at_unix_ms(&self) -> u64 {
+        self.created_at
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or(0)
+    }
+
+    // WebSocket and event stream clients alike.
+    pub fn connected_clients(&self) -> usize {
+        self.live_updates.subscriber_count() + self.event_log.subscriber_count()
+    }
+
+    // An open live connection keeps the room alive even when nobody votes.
+    fn is_idle(&self, idle_timeout: Duration) -> bool {
+        self.connected_clients() == 0 && self.idle_for() >= idle_timeout
+    }
+}
+
+pub struct RoomRegistry {
+    rooms: RwLock<HashMap<String, Arc<Room>>>,
+    max_rooms: usize,
+    idle_timeout: Duration,
+    creation_cooldown: Duration,
+    last_created_by: Mutex<HashMap<String, Instant>>,
+}
+
+impl RoomRegistry {
+    pub fn new(max_rooms: usize, idle_timeout: Duration, creation_cooldown: Duration) -> Self {
+        Self {
+            rooms: RwLock::new(HashMap::new()),
+            max_rooms,
+            idle_timeout,
+            creation_cooldown,
+            last_created_by: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // `creator` is the client address, which may create one room per cooldown.
+    pub async fn create(
+        &self,
+        settings: &AppSettings,
+        creator: &str,
+    ) -> Result<(String, Arc<Room>), RoomCreateError> {
+        let mut rooms = self.rooms.write().await;
+        if rooms.len() >= self.max_rooms {
+            return Err(RoomCreateError::LimitReached);
+        }
+        self.claim_creation(creator)?;
+        let id = loop {
+            let id = format!("{:08x}", rand::thread_rng().gen::<u32>());
+            if !rooms.contains_key(&id) {
+                break id;
+            }
+        };
+        let room = Arc::new(Room::start(settings).await);
+        rooms.insert(id.clone(), room.clone());
+        Ok((id, room))
+    }
+
+    fn claim_creation(&self, creator: &str) -> Result<(), RoomCreateError> {
+        if self.creation_cooldown.is_zero() {
+            return Ok(());
+        }
+        let mut last_created_by = self.last_created_by.lock().unwrap();
+        last_created_by.retain(|_, created_at| created_at.elapsed() < self.creation_cooldown);
+        if last_created_by.contains_key(creator) {
+            return Err(RoomCreateError::CreatedTooRecently);
+        }
+        last_created_by.insert(creator.to_owned(), Instant::now());
+        Ok(())
+    }
+
+    // Looking a room up counts as activity.
+    pub async fn get(&self, id: &str) -> Option<Arc<Room>> {
+        let room = self.rooms.read().await.get(id).cloned()?;
+        room.touch();
+        Some(room)
+    }
+
+    pub async fn list(&self) -> Vec<(String, Arc<Room>)> {
+        let mut rooms = self
+            .rooms
+            .read()
+            .await
+            .iter()
+            .map(|(id, room)| (id.clone(), room.clone()))
+            .collect::<Vec<_>>();
+        rooms.sort_by_key(|(_, room)| room.created_at);
+        rooms
+    }
+
+    pub async fn remove(&self, id: &str) -> Option<Arc<Room>> {
+        self.rooms.write().await.remove(id)
+    }
+
+    pub async fn remove_idle(&self) -> Vec<String> {
+        let mut rooms = self.rooms.write().await;
+        let idle = rooms
+            .iter()
+            .filter(|(_, room)| room.is_idle(self.idle_timeout))
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<String>>();
+        for id in &idle {
+            rooms.remove(id);
+        }
+        idle
+    }
+}
+
+pub fn run_room_collector(registry: Data<RoomRegistry>) -> JoinHandle<()> {
+    spawn(async move {
+        let mut collect = interval(ROOM_COLLECTOR_INTERVAL);
+        loop {
+            collect.tick().await;
+            registry.remove_idle().await;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::app_settings::AppSettings;
+
+    use super::{RoomCreateError, RoomRegistry};
+
+    #[actix_web::test]
+    async fn test_should_limit_rooms_and_collect_idle_ones() {
+        let registry = RoomRegistry::new(3, Duration::ZERO, Duration::ZERO);
+        let settings = AppSettings::default();
+        let (first, _) = registry
+            .create(&settings, "client")
+            .await
+            .expect("room should be created");
+        let (second, _) = registry
+            .create(&settings, "client")
+            .await
+            .expect("room should be created");
+        let (third, _) = registry
+            .create(&settings, "client")
+            .await
+            .expect("room should be created");
+        assert_ne!(first, second);
+        assert_eq!(
+            Some(RoomCreateError::LimitReached),
+            registry.create(&settings, "client").await.err()
+        );
+
+        let watched = registry.get(&second).await.unwrap();
+        let _receiver = watched.live_updates.subscribe();
+        let streamed = registry.get(&third).await.unwrap();
+        let _events = streamed.event_log.subscribe(None);
+        assert_eq!(vec![first.clone()], registry.remove_idle().await);
+        assert!(registry.get(&first).await.is_none());
+        assert_eq!(
+            vec![second.clone(), third.clone()],
+            registry
+                .list()
+                .await
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>()
+        );
+        assert!(registry.remove(&second).await.is_some());
+        assert!(registry.remove(&third).await.is_some());
+        assert!(registry.list().await.is_empty());
+    }
+
+    #[actix_web::test]
+    async fn test_should_reject_room_when_same_client_created_one_recently() {
+        let registry = RoomRegistry::new(4, Duration::from_secs(600), Duration::from_secs(60));
+        let settings = AppSettings::default();
+        assert!(registry.create(&settings, "10.0.0.1").await.is_ok());
+        assert_eq!(
+            Some(RoomCreateError::CreatedTooRecently),
+            registry.create(&settings, "10.0.0.1").await.err()
+        );
+        assert!(registry.create(&settings, "10.0.0.2").await.is_ok());
+    }
+}
@@ -0,0 +1,31 @@
+use actix_web::Error;
+
+use crate::{
+    app_settings::SettingsError,
+    http::error::{BadRequestError, ServiceUnavailableError, TooManyRequestsError},
+    rooms::RoomCreateError,
+};
+
+impl From<SettingsError> for BadRequestError {
+    fn from(error: SettingsError) -> Self {
+        BadRequestError::new(match error {
+            SettingsError::TickIntervalOutOfRange(_) => "Tick interval out of range",
+            SettingsError::BoardSizeOutOfRange { .. } => "Board size out of range",
+            SettingsError::AppleSpawnOneInZero => "Apple spawn rate has to be at least 1",
+            _ => "Invalid room configuration",
+        })
+    }
+}
+
+impl From<RoomCreateError> for Error {
+    fn from(error: RoomCreateError) -> Self {
+        match error {
+            RoomCreateError::LimitReached => {
+                ServiceUnavailableError::new("Room limit reached").into()
+            }
+            RoomCreateError::CreatedTooRecently => {
+                TooManyRequestsError::new("Wait before creating another room").into()
+            }
+        }
+    }
+}
@@ -0,0 +1,255 @@
+mod error;
+
+use std::sync::Arc;
+
+use actix_web::web::{Bytes, Data, Json, Path, Payload};
+use actix_web::{delete, get, post, Error, HttpRequest, HttpResponse};
+
+use crate::admin_api::admin_auth::AdminCaller;
+use crate::admin_api::audit_log::AuditLog;
+use crate::app_settings::AppSettings;
+use crate::http::error::{BadRequestError, NotFoundError};
+use crate::model::game_state::GameStateResponse;
+use crate::model::room::{RoomConfigRequest, RoomResponse};
+use crate::model::vote::VoteStatusResponse;
+use crate::rooms::{Room, RoomRegistry};
+use crate::web_api::{event_stream_response, open_live_board, register_vote, vote_status};
+
+async fn room_response(id: String, room: &Room) -> RoomResponse {
+    let state = room.snake_ctx.get_game_state().await;
+    RoomResponse {
+        id,
+        game_id: state.game_id,
+        tick: state.tick,
+        board: state.board,
+        score: state.stats.score,
+        connected_clients: room.connected_clients(),
+        idle_seconds: room.idle_for().as_secs(),
+        created_at_unix_ms: room.created_at_unix_ms(),
+    }
+}
+
+async fn find_room(registry: &RoomRegistry, id: &str) -> Result<Arc<Room>, NotFoundError> {
+    registry
+        .get(id)
+        .await
+        .ok_or(NotFoundError::new("Room not found"))
+}
+
+// An empty body creates a room with the server's [application] settings.
+#[post("/rooms")]
+async fn create_room(
+    request: HttpRequest,
+    registry: Data<RoomRegistry>,
+    defaults: Data<AppSettings>,
+    body: Bytes,
+) -> Result<HttpResponse, Error> {
+    let config = if body.iter().all(u8::is_ascii_whitespace) {
+        RoomConfigRequest::default()
+    } else {
+        serde_json::from_slice::<RoomConfigRequest>(&body)
+            .map_err(|_| BadRequestError::new("Invalid room configuration"))?
+    };
+    let settings = config.apply(&defaults);
+    settings.validate().map_err(BadRequestError::from)?;
+
+    let creator = request
+        .peer_addr()
+        .map(|address| address.ip().to_string())
+        .unwrap_or_else(|| "unknown".to_owned());
+    let (id, room) = registry.create(&settings, &creator).await?;
+    Ok(HttpResponse::Created().json(room_response(id, &room).await))
+}
+
+#[get("/rooms")]
+async fn list_rooms(registry: Data<RoomRegistry>) -> Json<Vec<RoomResponse>> {
+    let mut rooms = Vec::new();
+    for (id, room) in registry.list().await {
+        rooms.push(room_response(id, &room).await);
+    }
+    Json(rooms)
+}
+
+#[delete("/rooms/{id}")]
+async fn delete_room(
+    caller: AdminCaller,
+    registry: Data<RoomRegistry>,
+    audit_log: Data<AuditLog>,
+    id: Path<String>,
+) -> Result<HttpResponse, NotFoundError> {
+    let id = id.into_inner();
+    let removed = registry.remove(&id).await;
+    let outcome = if removed.is_some() { "ok" } else { "not found" };
+    audit_log.record(&caller.remote_addr, "delete_room", Some(id), outcome);
+    removed
+        .map(|_| HttpResponse::NoContent().finish())
+        .ok_or(NotFoundError::new("Room not found"))
+}
+
+#[get("/rooms/{id}/snake")]
+async fn show_room_plane(
+    registry: Data<RoomRegistry>,
+    id: Path<String>,
+) -> Result<String, NotFoundError> {
+    let room = find_room(&registry, &id).await?;
+    Ok(room.snake_ctx.get_plane_state().await)
+}
+
+#[get("/rooms/{id}/snake/state")]
+async fn show_room_state(
+    registry: Data<RoomRegistry>,
+    id: Path<String>,
+) -> Result<Json<GameStateResponse>, NotFoundError> {
+    let room = find_room(&registry, &id).await?;
+    Ok(Json(room.snake_ctx.get_game_state().await))
+}
+
+#[get("/rooms/{id}/snake/votes")]
+async fn show_room_votes(
+    registry: Data<RoomRegistry>,
+    id: Path<String>,
+) -> Result<Json<VoteStatusResponse>, NotFoundError> {
+    let room = find_room(&registry, &id).await?;
+    Ok(Json(
+        vote_status(&room.snake_ctx, &room.directions_container).await,
+    ))
+}
+
+#[get("/rooms/{id}/snake/events")]
+async fn room_events(
+    request: HttpRequest,
+    registry: Data<RoomRegistry>,
+    id: Path<String>,
+) -> Result<HttpResponse, NotFoundError> {
+    let room = find_room(&registry, &id).await?;
+    Ok(event_stream_response(&request, &room.event_log))
+}
+
+#[post("/rooms/{id}/snake/direction/{path}")]
+async fn request_room_direction(
+    request: HttpRequest,
+    registry: Data<RoomRegistry>,
+    path: Path<(String, String)>,
+) -> Result<HttpResponse, Error> {
+    let (id, direction) = path.into_inner();
+    let room = find_room(&registry, &id).await?;
+    register_vote(&request, &room.directions_container, direction).await
+}
+
+#[get("/rooms/{id}/ws")]
+async fn room_live_board(
+    request: HttpRequest,
+    body: Payload,
+    registry: Data<RoomRegistry>,
+    id: Path<String>,
+) -> Result<HttpResponse, Error> {
+    let room = find_room(&registry, &id).await?;
+    open_live_board(
+        &request,
+        body,
+        room.snake_ctx.clone(),
+        room.directions_container.clone(),
+        &room.live_updates,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::time::Duration;
+
+    use actix_web::cookie::Key;
+    use actix_web::web::Data;
+    use actix_web::{test, App};
+
+    use super::{create_room, delete_room, list_rooms, request_room_direction, show_room_state};
+    use crate::admin_api::admin_auth::AdminToken;
+    use crate::admin_api::audit_log::AuditLog;
+    use crate::app_settings::AppSettings;
+    use crate::rooms::RoomRegistry;
+    use crate::voter_identity::VoterIdentity;
+
+    #[actix_web::test]
+    async fn test_should_serve_each_room_separately_when_rooms_are_created() {
+        let registry = Data::new(RoomRegistry::new(
+            1,
+            Duration::from_secs(600),
+            Duration::ZERO,
+        ));
+        let app = test::init_service(
+            App::new()
+                .app_data(registry.clone())
+                .app_data(Data::new(AppSettings::default()))
+                .app_data(Data::new(AdminToken(Some("secret".to_owned()))))
+                .app_data(Data::new(AuditLog::new()))
+                .app_data(Data::new(VoterIdentity {
+                    header: None,
+                    tokens: HashSet::new(),
+                    key: Key::generate(),
+                }))
+                .service(create_room)
+                .service(list_rooms)
+                .service(delete_room)
+                .service(show_room_state)
+                .service(request_room_direction),
+        )
+        .await;
+
+        let request = test::TestRequest::post()
+            .uri("/rooms")
+            .set_payload(r#"{"board-width": 30, "board-height": 15}"#)
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(201, response.status().as_u16());
+        let room: serde_json::Value = test::read_body_json(response).await;
+        let id = room["id"].as_str().unwrap().to_owned();
+        assert_eq!(30, room["board"]["width"]);
+
+        let request = test::TestRequest::post().uri("/rooms").to_request();
+        assert_eq!(
+            503,
+            test::call_service(&app, request).await.status().as_u16()
+        );
+        let request = test::TestRequest::post()
+            .uri("/rooms")
+            .set_payload(r#"{"board-width": 2}"#)
+            .to_request();
+        assert_eq!(
+            400,
+            test::call_service(&app, request).await.status().as_u16()
+        );
+
+        let request = test::TestRequest::get()
+            .uri(&format!("/rooms/{}/snake/state", id))
+            .to_request();
+        let state: serde_json::Value = test::call_and_read_body_json(&app, request).await;
+        assert_eq!(15, state["board"]["height"]);
+        let request = test::TestRequest::post()
+            .uri(&format!("/rooms/{}/snake/direction/up", id))
+            .peer_addr("203.0.113.7:5000".parse().unwrap())
+            .to_request();
+        assert_eq!(
+            200,
+            test::call_service(&app, request).await.status().as_u16()
+        );
+        let request = test::TestRequest::get().uri("/rooms").to_request();
+        let rooms: serde_json::Value = test::call_and_read_body_json(&app, request).await;
+        assert_eq!(1, rooms.as_array().unwrap().len());
+
+        let request = test::TestRequest::delete()
+            .uri(&format!("/rooms/{}", id))
+            .insert_header(("Authorization", "Bearer secret"))
+            .to_request();
+        assert_eq!(
+            204,
+            test::call_service(&app, request).await.status().as_u16()
+        );
+        let request = test::TestRequest::get()
+            .uri(&format!("/rooms/{}/snake/state", id))
+            .to_request();
+        assert_eq!(
+            404,
+            test::call_service(&app, request).await.status().as_u16()
+        );
+    }
+}
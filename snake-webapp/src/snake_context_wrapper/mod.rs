@@ -1,17 +1,26 @@
+use std::time::{Duration, SystemTime};
+
 use tokio::sync::RwLock;
 
-use snake_core::SnakeContext;
+use snake_core::game_config::GameConfig;
+use snake_core::{SnakeContext, TickResult};
 
 use crate::model::direction_request::DirectionRequest;
+use crate::model::game_state::GameStateResponse;
 
 pub struct SnakeWebAppContext {
     snake_ctx: RwLock<SnakeContext>,
+    next_tick_at: RwLock<Option<SystemTime>>,
+    // Set by operators at runtime; takes precedence over the speed policy.
+    tick_interval_override: RwLock<Option<u64>>,
 }
 
 impl SnakeWebAppContext {
-    pub fn new() -> Self {
+    pub fn new(config: GameConfig) -> Self {
         Self {
-            snake_ctx: RwLock::new(SnakeContext::new())
+            snake_ctx: RwLock::new(SnakeContext::with_config(config)),
+            next_tick_at: RwLock::new(None),
+            tick_interval_override: RwLock::new(None),
         }
     }
 
@@ -19,6 +28,10 @@ impl SnakeWebAppContext {
         self.snake_ctx.write().await.new_game()
     }
 
+    pub async fn game_id(&self) -> u64 {
+        self.snake_ctx.read().await.game_id()
+    }
+
     pub async fn current_direction(&self) -> DirectionRequest {
         self.snake_ctx.read().await.current_direction().into()
     }
@@ -27,7 +40,48 @@ impl SnakeWebAppContext {
         self.snake_ctx.read().await.get_plane_string()
     }
 
-    pub async fn update_game_state(&self) {
+    pub async fn get_game_state(&self) -> GameStateResponse {
+        let next_tick_at = *self.next_tick_at.read().await;
+        let snake_ctx = self.snake_ctx.read().await;
+        GameStateResponse::new(
+            &snake_ctx.snapshot(),
+            snake_ctx.stats(),
+            snake_ctx.game_id(),
+            next_tick_at,
+        )
+    }
+
+    pub async fn schedule_next_tick(&self) -> Duration {
+        let tick_interval = Duration::from_millis(self.tick_interval_milliseconds().await);
+        *self.next_tick_at.write().await = Some(SystemTime::now() + tick_interval);
+        tick_interval
+    }
+
+    pub async fn cancel_next_tick(&self) {
+        *self.next_tick_at.write().await = None;
+    }
+
+    // `None` while no tick is scheduled, e.g. when the scheduler is paused.
+    pub async fn milliseconds_until_next_tick(&self) -> Option<u64> {
+        let next_tick_at = (*self.next_tick_at.read().await)?;
+        let remaining = next_tick_at
+            .duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO);
+        Some(remaining.as_millis() as u64)
+    }
+
+    pub async fn tick_interval_milliseconds(&self) -> u64 {
+        match *self.tick_interval_override.read().await {
+            Some(milliseconds) => milliseconds,
+            None => self.snake_ctx.read().await.tick_interval_milliseconds(),
+        }
+    }
+
+    pub async fn set_tick_interval_milliseconds(&self, milliseconds: Option<u64>) {
+        *self.tick_interval_override.write().await = milliseconds;
+    }
+
+    pub async fn update_game_state(&self) -> TickResult {
         self.snake_ctx.write().await.update_position()
     }
 
@@ -0,0 +1,36 @@
+use crate::model::direction_request::DirectionRequest;
+
+use super::VoteResolver;
+
+pub struct FirstVote;
+
+impl VoteResolver for FirstVote {
+    fn resolve(
+        &mut self,
+        votes: &[DirectionRequest],
+        _current_direction: DirectionRequest,
+    ) -> Option<DirectionRequest> {
+        votes.first().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::direction_request::DirectionRequest;
+    use crate::vote_resolver::VoteResolver;
+
+    use super::FirstVote;
+
+    #[test]
+    fn test_should_pick_earliest_vote_when_later_votes_outnumber_it() {
+        let votes = [
+            DirectionRequest::Left,
+            DirectionRequest::Up,
+            DirectionRequest::Up,
+        ];
+        assert_eq!(
+            Some(DirectionRequest::Left),
+            FirstVote.resolve(&votes, DirectionRequest::Up)
+        );
+    }
+}
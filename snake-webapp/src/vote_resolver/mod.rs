@@ -0,0 +1,36 @@
+pub mod first_vote;
+pub mod plurality;
+pub mod proportional;
+pub mod supermajority;
+
+use crate::model::direction_request::DirectionRequest;
+
+pub use self::first_vote::FirstVote;
+pub use self::plurality::{Plurality, TieBreak};
+pub use self::proportional::Proportional;
+pub use self::supermajority::Supermajority;
+
+// Checked in this order wherever votes are counted, so ties resolve the same way every time.
+pub const DIRECTIONS: [DirectionRequest; 4] = [
+    DirectionRequest::Up,
+    DirectionRequest::Down,
+    DirectionRequest::Left,
+    DirectionRequest::Right,
+];
+
+pub trait VoteResolver {
+    // `votes` are in the order they were cast, never empty and never contain
+    // the reverse of `current_direction`. `None` keeps the snake going straight.
+    fn resolve(
+        &mut self,
+        votes: &[DirectionRequest],
+        current_direction: DirectionRequest,
+    ) -> Option<DirectionRequest>;
+}
+
+pub fn count_votes(votes: &[DirectionRequest]) -> [(DirectionRequest, usize); 4] {
+    DIRECTIONS.map(|direction| {
+        let count = votes.iter().filter(|&&vote| vote == direction).count();
+        (direction, count)
+    })
+}
@@ -0,0 +1,98 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use crate::model::direction_request::DirectionRequest;
+
+use super::{count_votes, VoteResolver};
+
+pub enum TieBreak {
+    // Going straight wins a tie it is part of, otherwise the first of up, down, left, right.
+    Deterministic,
+    Seeded(Box<StdRng>),
+}
+
+pub struct Plurality {
+    pub tie_break: TieBreak,
+}
+
+impl VoteResolver for Plurality {
+    fn resolve(
+        &mut self,
+        votes: &[DirectionRequest],
+        current_direction: DirectionRequest,
+    ) -> Option<DirectionRequest> {
+        let counts = count_votes(votes);
+        let most_votes = counts.iter().map(|&(_, count)| count).max()?;
+        let leaders = counts
+            .iter()
+            .filter(|&&(_, count)| count == most_votes && count > 0)
+            .map(|&(direction, _)| direction)
+            .collect::<Vec<DirectionRequest>>();
+
+        match &mut self.tie_break {
+            _ if leaders.len() == 1 => leaders.first().copied(),
+            TieBreak::Deterministic if leaders.contains(&current_direction) => {
+                Some(current_direction)
+            }
+            TieBreak::Deterministic => leaders.first().copied(),
+            TieBreak::Seeded(rng) => leaders.choose(rng.as_mut()).copied(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::model::direction_request::DirectionRequest;
+    use crate::vote_resolver::VoteResolver;
+
+    use super::{Plurality, TieBreak};
+
+    #[test]
+    fn test_should_pick_most_voted_direction_when_votes_differ() {
+        let mut plurality = Plurality {
+            tie_break: TieBreak::Deterministic,
+        };
+        let votes = [
+            DirectionRequest::Left,
+            DirectionRequest::Up,
+            DirectionRequest::Up,
+            DirectionRequest::Right,
+        ];
+        assert_eq!(
+            Some(DirectionRequest::Up),
+            plurality.resolve(&votes, DirectionRequest::Right)
+        );
+    }
+
+    #[test]
+    fn test_should_break_ties_reproducibly_when_votes_are_tied() {
+        let votes = [DirectionRequest::Left, DirectionRequest::Up];
+        let mut plurality = Plurality {
+            tie_break: TieBreak::Deterministic,
+        };
+        assert_eq!(
+            Some(DirectionRequest::Left),
+            plurality.resolve(&votes, DirectionRequest::Left)
+        );
+        assert_eq!(
+            Some(DirectionRequest::Up),
+            plurality.resolve(&votes, DirectionRequest::Right)
+        );
+
+        let picks = |seed| {
+            let mut plurality = Plurality {
+                tie_break: TieBreak::Seeded(Box::new(StdRng::seed_from_u64(seed))),
+            };
+            (0..16)
+                .map(|_| plurality.resolve(&votes, DirectionRequest::Right).unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(picks(7), picks(7));
+        assert!(picks(7)
+            .iter()
+            .all(|pick| [DirectionRequest::Left, DirectionRequest::Up].contains(pick)));
+    }
+}
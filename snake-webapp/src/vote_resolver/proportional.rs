@@ -0,0 +1,51 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use crate::model::direction_request::DirectionRequest;
+
+use super::VoteResolver;
+
+// Draws one of the votes, so a direction wins as often as its share of the votes.
+pub struct Proportional {
+    pub rng: StdRng,
+}
+
+impl VoteResolver for Proportional {
+    fn resolve(
+        &mut self,
+        votes: &[DirectionRequest],
+        _current_direction: DirectionRequest,
+    ) -> Option<DirectionRequest> {
+        votes.choose(&mut self.rng).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::model::direction_request::DirectionRequest;
+    use crate::vote_resolver::VoteResolver;
+
+    use super::Proportional;
+
+    #[test]
+    fn test_should_follow_vote_share_when_resolving_many_ticks() {
+        let mut proportional = Proportional {
+            rng: StdRng::seed_from_u64(42),
+        };
+        let votes = [
+            DirectionRequest::Up,
+            DirectionRequest::Up,
+            DirectionRequest::Up,
+            DirectionRequest::Left,
+        ];
+        let ups = (0..4000)
+            .filter(|_| {
+                proportional.resolve(&votes, DirectionRequest::Right) == Some(DirectionRequest::Up)
+            })
+            .count();
+        assert!((2800..3200).contains(&ups), "up won {} of 4000 ticks", ups);
+    }
+}
@@ -0,0 +1,51 @@
+use crate::model::direction_request::DirectionRequest;
+
+use super::{count_votes, VoteResolver};
+
+// Turns only when one direction has at least `percent` of the votes.
+pub struct Supermajority {
+    pub percent: u8,
+}
+
+impl VoteResolver for Supermajority {
+    fn resolve(
+        &mut self,
+        votes: &[DirectionRequest],
+        _current_direction: DirectionRequest,
+    ) -> Option<DirectionRequest> {
+        count_votes(votes)
+            .into_iter()
+            .find(|&(_, count)| count * 100 >= usize::from(self.percent) * votes.len())
+            .map(|(direction, _)| direction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::direction_request::DirectionRequest;
+    use crate::vote_resolver::VoteResolver;
+
+    use super::Supermajority;
+
+    #[test]
+    fn test_should_keep_going_straight_when_no_direction_reaches_threshold() {
+        let mut supermajority = Supermajority { percent: 67 };
+        let split = [
+            DirectionRequest::Up,
+            DirectionRequest::Up,
+            DirectionRequest::Left,
+        ];
+        assert_eq!(None, supermajority.resolve(&split, DirectionRequest::Right));
+
+        let clear = [
+            DirectionRequest::Up,
+            DirectionRequest::Up,
+            DirectionRequest::Up,
+            DirectionRequest::Left,
+        ];
+        assert_eq!(
+            Some(DirectionRequest::Up),
+            supermajority.resolve(&clear, DirectionRequest::Right)
+        );
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::HashSet;
+
+use actix_web::cookie::{Cookie, CookieJar, Key, SameSite};
+use actix_web::web::Data;
+use actix_web::HttpRequest;
+
+use crate::http::error::{BadRequestError, ForbiddenError};
+
+pub const VOTER_COOKIE: &str = "snake_voter";
+const MAX_VOTER_ID_LENGTH: usize = 128;
+
+// How voters are told apart: a header set by an authenticating proxy, a
+// configured API token, a cookie signed with `key` or else the client address.
+pub struct VoterIdentity {
+    pub header: Option<String>,
+    pub tokens: HashSet<String>,
+    pub key: Key,
+}
+
+// Ids from different sources are prefixed so they can never collide.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VoterId(String);
+
+pub struct Voter {
+    pub id: VoterId,
+    // Set for voters known only by address, so they keep their id when it changes.
+    pub new_cookie: Option<Cookie<'static>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum VoterIdentityError {
+    Invalid,
+    UnknownToken,
+    Missing,
+}
+
+impl VoterId {
+    pub fn new(source: &str, id: &str) -> Self {
+        Self(format!("{}:{}", source, id))
+    }
+}
+
+impl From<VoterIdentityError> for actix_web::Error {
+    fn from(error: VoterIdentityError) -> Self {
+        match error {
+            VoterIdentityError::Invalid => BadRequestError::new("Invalid voter identity").into(),
+            VoterIdentityError::UnknownToken => ForbiddenError::new("Unknown voter token").into(),
+            VoterIdentityError::Missing => ForbiddenError::new("Cannot identify voter").into(),
+        }
+    }
+}
+
+impl Voter {
+    // The cookie only ever carries an id the server derived from the client
+    // address, so fetching a fresh one per vote still counts as the same voter.
+    pub fn identify(request: &HttpRequest) -> Result<Self, VoterIdentityError> {
+        let identity = request
+            .app_data::<Data<VoterIdentity>>()
+            .ok_or(VoterIdentityError::Missing)?;
+        let header = identity
+            .header
+            .as_deref()
+            .and_then(|header| request.headers().get(header))
+            .and_then(|value| value.to_str().ok());
+        let token = request
+            .headers()
+            .get("Authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+
+        let (source, id) = match (header, token) {
+            (Some(header), _) => ("header", header.trim()),
+            (None, Some(token)) if identity.tokens.contains(token) => ("token", token),
+            (None, Some(_)) => return Err(VoterIdentityError::UnknownToken),
+            (None, None) => return Self::from_cookie_or_address(request, &identity.key),
+        };
+        if id.is_empty() || id.len() > MAX_VOTER_ID_LENGTH {
+            return Err(VoterIdentityError::Invalid);
+        }
+        Ok(Self {
+            id: VoterId::new(source, id),
+            new_cookie: None,
+        })
+    }
+
+    fn from_cookie_or_address(
+        request: &HttpRequest,
+        key: &Key,
+    ) -> Result<Self, VoterIdentityError> {
+        let cookie_id = request
+            .cookie(VOTER_COOKIE)
+            .and_then(|cookie| verified_cookie_value(key, cookie));
+        if let Some(id) = cookie_id {
+            return Ok(Self {
+                id: VoterId(id),
+                new_cookie: None,
+            });
+        }
+        let address = request.peer_addr().ok_or(VoterIdentityError::Missing)?;
+        let id = VoterId::new("address", &address.ip().to_string());
+        Ok(Self {
+            new_cookie: Some(signed_voter_cookie(key, id.0.clone())),
+            id,
+        })
+    }
+}
+
+// Cookies signed with another key, e.g. before a restart, are ignored.
+fn verified_cookie_value(key: &Key, cookie: Cookie<'static>) -> Option<String> {
+    let mut jar = CookieJar::new();
+    jar.add_original(cookie);
+    let verified = jar.signed(key).get(VOTER_COOKIE)?;
+    Some(verified.value().to_owned())
+}
+
+fn signed_voter_cookie(key: &Key, id: String) -> Cookie<'static> {
+    let cookie = Cookie::build(VOTER_COOKIE, id)
+        .path("/")
+        .http_only(true)
+        .same_site(SameSite::Lax)
+        .permanent()
+        .finish();
+    let mut jar = CookieJar::new();
+    jar.signed_mut(key).add(cookie);
+    jar.get(VOTER_COOKIE)
+        .cloned()
+        .expect("signed cookie should be in the jar")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use actix_web::cookie::{Cookie, Key};
+    use actix_web::test::TestRequest;
+    use actix_web::web::Data;
+
+    use super::{
+        signed_voter_cookie, Voter, VoterId, VoterIdentity, VoterIdentityError, VOTER_COOKIE,
+    };
+
+    fn voter_identity(header: Option<&str>) -> Data<VoterIdentity> {
+        Data::new(VoterIdentity {
+            header: header.map(str::to_owned),
+            tokens: HashSet::from(["token".to_owned()]),
+            key: Key::generate(),
+        })
+    }
+
+    #[test]
+    fn test_should_prefer_configured_header_then_known_token_then_signed_cookie_then_address() {
+        let identity = voter_identity(Some("X-Voter-Id"));
+        let cookie = signed_voter_cookie(&identity.key, "address:10.0.0.1".to_owned());
+        let request = TestRequest::default()
+            .app_data(identity.clone())
+            .insert_header(("X-Voter-Id", "alice"))
+            .insert_header(("Authorization", "Bearer token"))
+            .to_http_request();
+        assert_eq!(
+            VoterId::new("header", "alice"),
+            Voter::identify(&request).unwrap().id
+        );
+
+        let request = TestRequest::default()
+            .app_data(identity.clone())
+            .insert_header(("Authorization", "Bearer token"))
+            .cookie(cookie.clone())
+            .to_http_request();
+        assert_eq!(
+            VoterId::new("token", "token"),
+            Voter::identify(&request).unwrap().id
+        );
+
+        let request = TestRequest::default()
+            .app_data(identity.clone())
+            .peer_addr("10.0.0.2:4000".parse().unwrap())
+            .cookie(cookie)
+            .to_http_request();
+        let voter = Voter::identify(&request).unwrap();
+        assert_eq!(VoterId::new("address", "10.0.0.1"), voter.id);
+        assert!(voter.new_cookie.is_none());
+
+        let request = TestRequest::default()
+            .app_data(identity.clone())
+            .peer_addr("10.0.0.2:4000".parse().unwrap())
+            .to_http_request();
+        let voter = Voter::identify(&request).unwrap();
+        assert_eq!(VoterId::new("address", "10.0.0.2"), voter.id);
+        assert!(voter.new_cookie.is_some());
+    }
+
+    #[test]
+    fn test_should_reject_unknown_token_and_fall_back_to_address_when_cookie_is_forged() {
+        let identity = voter_identity(None);
+        let request = TestRequest::default()
+            .app_data(identity.clone())
+            .insert_header(("Authorization", "Bearer made-up"))
+            .to_http_request();
+        assert!(matches!(
+            Voter::identify(&request),
+            Err(VoterIdentityError::UnknownToken)
+        ));
+
+        let forged = [
+            Cookie::new(VOTER_COOKIE, "header:bob"),
+            signed_voter_cookie(&Key::generate(), "header:bob".to_owned()),
+        ];
+        for cookie in forged {
+            let request = TestRequest::default()
+                .app_data(identity.clone())
+                .peer_addr("10.0.0.3:4000".parse().unwrap())
+                .cookie(cookie.clone())
+                .to_http_request();
+            assert_eq!(
+                VoterId::new("address", "10.0.0.3"),
+                Voter::identify(&request).unwrap().id
+            );
+
+            let request = TestRequest::default()
+                .app_data(identity.clone())
+                .cookie(cookie)
+                .to_http_request();
+            assert!(matches!(
+                Voter::identify(&request),
+                Err(VoterIdentityError::Missing)
+            ));
+        }
+    }
+}
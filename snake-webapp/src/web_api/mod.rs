@@ -1,32 +1,252 @@
 mod error;
 
-use actix_web::web::Data;
-use actix_web::{get, post, web::Path, Responder};
+use actix_web::web::{Data, Json, Payload};
+use actix_web::{get, post, rt::spawn, web::Path, Error, HttpRequest, HttpResponse, Responder};
 
 use crate::directions_container::DirectionsContainer;
+use crate::event_stream::{event_stream, EventLog};
 use crate::http::error::NotFoundError;
-use crate::model::direction_request::{DirectionRequest, UnknownDirectionError};
+use crate::live_updates::{serve_websocket, LiveUpdates};
+use crate::model::direction_request::DirectionRequest;
+use crate::model::vote::{VoteResponse, VoteStatusResponse};
 use crate::snake_context_wrapper::SnakeWebAppContext;
+use crate::voter_identity::Voter;
 
 #[get("/snake")]
 async fn show_plane(game: actix_web::web::Data<SnakeWebAppContext>) -> impl Responder {
-    let plane = game.get_plane_state().await;
-    format!("{}", plane)
+    game.get_plane_state().await
+}
+
+#[get("/snake/state")]
+async fn show_state(game: Data<SnakeWebAppContext>) -> impl Responder {
+    Json(game.get_game_state().await)
+}
+
+#[get("/snake/votes")]
+async fn show_votes(
+    game: Data<SnakeWebAppContext>,
+    directions_container: Data<DirectionsContainer>,
+) -> impl Responder {
+    Json(vote_status(&game, &directions_container).await)
+}
+
+#[get("/snake/ws")]
+async fn live_board(
+    request: HttpRequest,
+    body: Payload,
+    game: Data<SnakeWebAppContext>,
+    directions_container: Data<DirectionsContainer>,
+    live_updates: Data<LiveUpdates>,
+) -> Result<HttpResponse, Error> {
+    open_live_board(&request, body, game, directions_container, &live_updates)
+}
+
+#[get("/snake/events")]
+async fn game_events(request: HttpRequest, event_log: Data<EventLog>) -> HttpResponse {
+    event_stream_response(&request, &event_log)
 }
 
 #[post("/snake/direction/{path}")]
 async fn request_direction(
+    request: HttpRequest,
     directions_container: Data<DirectionsContainer>,
     path: Path<String>,
-) -> Result<String, NotFoundError> {
-    let direction: DirectionRequest =
-        path.clone()
-            .try_into()
-            .map_err(|unknown_direction_err: UnknownDirectionError|  NotFoundError::from(unknown_direction_err))?;
-
-    directions_container
-        .register_direction_request(direction)
+) -> Result<HttpResponse, Error> {
+    register_vote(&request, &directions_container, path.into_inner()).await
+}
+
+// Shared with the per-room routes, which resolve the game parts from a room.
+pub(crate) fn open_live_board(
+    request: &HttpRequest,
+    body: Payload,
+    game: Data<SnakeWebAppContext>,
+    directions_container: Data<DirectionsContainer>,
+    live_updates: &LiveUpdates,
+) -> Result<HttpResponse, Error> {
+    let (response, session, incoming) = actix_ws::handle(request, body)?;
+    // Subscribe before the snapshot is taken so no tick falls in between.
+    let receiver = live_updates.subscribe();
+    spawn(serve_websocket(
+        session,
+        incoming,
+        receiver,
+        game,
+        directions_container,
+    ));
+    Ok(response)
+}
+
+pub(crate) async fn vote_status(
+    game: &SnakeWebAppContext,
+    directions_container: &DirectionsContainer,
+) -> VoteStatusResponse {
+    VoteStatusResponse {
+        tick: game.get_game_state().await.tick,
+        votes: directions_container.visible_tally().await,
+        voters: directions_container.voter_count().await,
+        blind: directions_container.is_blind(),
+        milliseconds_until_tick: game.milliseconds_until_next_tick().await,
+    }
+}
+
+pub(crate) fn event_stream_response(request: &HttpRequest, event_log: &EventLog) -> HttpResponse {
+    let last_event_id = request
+        .headers()
+        .get("Last-Event-ID")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok());
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .streaming(event_stream(event_log, last_event_id))
+}
+
+// The direction is checked first, so an unknown one is a 404 for anybody.
+pub(crate) async fn register_vote(
+    request: &HttpRequest,
+    directions_container: &DirectionsContainer,
+    path: String,
+) -> Result<HttpResponse, Error> {
+    let direction: DirectionRequest = path.try_into().map_err(NotFoundError::from)?;
+    let voter = Voter::identify(request)?;
+
+    let outcome = directions_container
+        .register_direction_request(voter.id, direction)
         .await
-        .map_err(|register_error| NotFoundError::from(register_error))?;
-    Ok("Success".into())
+        .map_err(NotFoundError::from)?;
+    let mut response = HttpResponse::Ok();
+    if let Some(cookie) = voter.new_cookie {
+        response.cookie(cookie);
+    }
+    Ok(response.json(VoteResponse { direction, outcome }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use actix_web::cookie::{Cookie, Key};
+    use actix_web::web::Data;
+    use actix_web::{test, App};
+    use snake_core::game_config::GameConfig;
+
+    use super::{request_direction, show_votes};
+    use crate::directions_container::DirectionsContainer;
+    use crate::snake_context_wrapper::SnakeWebAppContext;
+    use crate::voter_identity::{VoterIdentity, VOTER_COOKIE};
+
+    fn voter_identity(tokens: &[&str]) -> Data<VoterIdentity> {
+        Data::new(VoterIdentity {
+            header: None,
+            tokens: tokens
+                .iter()
+                .map(|token| token.to_string())
+                .collect::<HashSet<_>>(),
+            key: Key::generate(),
+        })
+    }
+
+    #[actix_web::test]
+    async fn test_should_report_voters_and_hide_tally_when_votes_are_blind() {
+        for blind in [false, true] {
+            let snake_ctx = Data::new(SnakeWebAppContext::new(GameConfig::default()));
+            snake_ctx.new_game().await;
+            snake_ctx.schedule_next_tick().await;
+            let app = test::init_service(
+                App::new()
+                    .app_data(snake_ctx)
+                    .app_data(Data::new(DirectionsContainer::init_container(16, blind)))
+                    .app_data(voter_identity(&["a", "b"]))
+                    .service(request_direction)
+                    .service(show_votes),
+            )
+            .await;
+            for (voter, direction) in [("a", "up"), ("b", "up"), ("a", "left")] {
+                let request = test::TestRequest::post()
+                    .uri(&format!("/snake/direction/{}", direction))
+                    .insert_header(("Authorization", format!("Bearer {}", voter)))
+                    .to_request();
+                test::call_service(&app, request).await;
+            }
+
+            let request = test::TestRequest::get().uri("/snake/votes").to_request();
+            let status: serde_json::Value = test::call_and_read_body_json(&app, request).await;
+            assert_eq!(2, status["voters"]);
+            assert_eq!(blind, status["blind"]);
+            assert!(status["milliseconds_until_tick"].as_u64().unwrap() <= 10_000);
+            if blind {
+                assert!(status["votes"].is_null());
+            } else {
+                assert_eq!(1, status["votes"]["up"]);
+                assert_eq!(1, status["votes"]["left"]);
+            }
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_should_count_one_voter_when_every_vote_brings_a_fresh_cookie() {
+        let snake_ctx = Data::new(SnakeWebAppContext::new(GameConfig::default()));
+        snake_ctx.new_game().await;
+        let app = test::init_service(
+            App::new()
+                .app_data(snake_ctx)
+                .app_data(Data::new(DirectionsContainer::init_container(64, false)))
+                .app_data(voter_identity(&["known"]))
+                .service(request_direction)
+                .service(show_votes),
+        )
+        .await;
+        let vote = |direction: &str| {
+            test::TestRequest::post()
+                .uri(&format!("/snake/direction/{}", direction))
+                .peer_addr("203.0.113.7:5000".parse().unwrap())
+        };
+
+        let unidentified = test::TestRequest::post()
+            .uri("/snake/direction/sideways")
+            .to_request();
+        assert_eq!(
+            404,
+            test::call_service(&app, unidentified)
+                .await
+                .status()
+                .as_u16()
+        );
+        let unknown_token = vote("up")
+            .insert_header(("Authorization", "Bearer random"))
+            .to_request();
+        assert_eq!(
+            403,
+            test::call_service(&app, unknown_token)
+                .await
+                .status()
+                .as_u16()
+        );
+
+        for attempt in 0..10 {
+            let response = test::call_service(&app, vote("up").to_request()).await;
+            assert_eq!(200, response.status().as_u16());
+            let fresh_cookie = response
+                .response()
+                .cookies()
+                .find(|cookie| cookie.name() == VOTER_COOKIE)
+                .expect("a vote without a cookie should issue one")
+                .into_owned();
+            let forged_cookie = Cookie::new(VOTER_COOKIE, format!("header:voter-{}", attempt));
+            for cookie in [fresh_cookie, forged_cookie] {
+                let request = vote("left").cookie(cookie).to_request();
+                assert_eq!(
+                    200,
+                    test::call_service(&app, request).await.status().as_u16()
+                );
+            }
+        }
+
+        let request = test::TestRequest::get().uri("/snake/votes").to_request();
+        let status: serde_json::Value = test::call_and_read_body_json(&app, request).await;
+        assert_eq!(1, status["voters"]);
+        assert_eq!(1, status["votes"]["left"]);
+        assert_eq!(0, status["votes"]["up"]);
+    }
 }
@@ -0,0 +1,232 @@
+"use strict";
+
+const COLORS = {
+    background: "#111216",
+    grid: "#1b1c21",
+    wall: "#6b6e78",
+    apple: "#e5534b",
+    creature: "#c678dd",
+    body: "#57a64a",
+    head: "#7bc96f",
+};
+const REASONS = {
+    self_collision: "The snake bit itself",
+    wall_collision: "The snake hit a wall",
+    caught_by_arena: "The arena closed in",
+    creature_contact: "A creature caught the snake",
+    time_up: "Time is up",
+    target_reached: "Target reached",
+};
+const KEYS = {
+    ArrowUp: "up",
+    ArrowDown: "down",
+    ArrowLeft: "left",
+    ArrowRight: "right",
+};
+const GAME_OVER_BANNER_MS = 3000;
+const POLL_INTERVAL_MS = 1000;
+const VOTES_POLL_INTERVAL_MS = 250;
+
+const canvas = document.getElementById("board");
+const context = canvas.getContext("2d");
+const connection = document.getElementById("connection");
+const countdown = document.getElementById("countdown");
+const gameOver = document.getElementById("game-over");
+
+let state = null;
+let votedFor = null;
+let pollTimer = null;
+let gameOverTimer = null;
+// Local monotonic time of the next tick, derived from the server's relative
+// countdown so a skewed client clock does not matter.
+let tickDeadline = null;
+
+function render() {
+    if (!state) {
+        return;
+    }
+    const { width, height } = state.board;
+    const cell = Math.max(4, Math.floor(600 / width));
+    if (canvas.width !== width * cell || canvas.height !== height * cell) {
+        canvas.width = width * cell;
+        canvas.height = height * cell;
+    }
+
+    context.fillStyle = COLORS.background;
+    context.fillRect(0, 0, canvas.width, canvas.height);
+    context.strokeStyle = COLORS.grid;
+    for (let x = 0; x <= width; x++) {
+        context.beginPath();
+        context.moveTo(x * cell + 0.5, 0);
+        context.lineTo(x * cell + 0.5, canvas.height);
+        context.stroke();
+    }
+    for (let y = 0; y <= height; y++) {
+        context.beginPath();
+        context.moveTo(0, y * cell + 0.5);
+        context.lineTo(canvas.width, y * cell + 0.5);
+        context.stroke();
+    }
+
+    const fill = (point, color, inset) => {
+        context.fillStyle = color;
+        context.fillRect(point.x * cell + inset, point.y * cell + inset, cell - 2 * inset, cell - 2 * inset);
+    };
+    state.walls.forEach((wall) => fill(wall, COLORS.wall, 0));
+    state.apples.forEach((apple) => fill(apple, COLORS.apple, cell / 5));
+    state.creatures.forEach((creature) => fill(creature, COLORS.creature, cell / 8));
+    state.snake.forEach((part) => fill(part, COLORS.body, 1));
+    if (state.head) {
+        fill(state.head, COLORS.head, 1);
+    }
+}
+
+function showState(nextState) {
+    if (state && state.tick !== nextState.tick) {
+        votedFor = null;
+        markVote();
+    }
+    state = nextState;
+    document.getElementById("score").textContent = state.stats.score;
+    document.getElementById("length").textContent = state.snake.length;
+    document.getElementById("lives").textContent = state.stats.lives_left;
+    document.getElementById("tick").textContent = state.tick;
+    render();
+}
+
+function showVotes(votes) {
+    for (const direction of ["up", "down", "left", "right"]) {
+        document.getElementById(`votes-${direction}`).textContent = votes[direction];
+    }
+}
+
+function markVote() {
+    document.querySelectorAll(".vote").forEach((button) => {
+        button.classList.toggle("selected", button.dataset.direction === votedFor);
+    });
+}
+
+function updateCountdown() {
+    if (tickDeadline === null) {
+        countdown.textContent = "–";
+        return;
+    }
+    const remaining = Math.max(0, tickDeadline - performance.now());
+    countdown.textContent = `${(remaining / 1000).toFixed(1)} s`;
+}
+
+function setOnline(online) {
+    connection.textContent = online ? "live" : "reconnecting…";
+    connection.classList.toggle("online", online);
+}
+
+// Keeps the tally and the countdown live between ticks.
+async function refreshVotes() {
+    try {
+        const response = await fetch("/snake/votes");
+        if (!response.ok) {
+            return;
+        }
+        const status = await response.json();
+        // Blind voting hides the votes of the running tick.
+        if (status.votes) {
+            showVotes(status.votes);
+        }
+        tickDeadline = status.milliseconds_until_tick === null
+            ? null
+            : performance.now() + status.milliseconds_until_tick;
+    } catch (error) {
+        // The live board reports the connection state.
+    }
+}
+
+async function vote(direction) {
+    try {
+        const response = await fetch(`/snake/direction/${direction}`, { method: "POST" });
+        if (response.ok) {
+            votedFor = direction;
+            markVote();
+            refreshVotes();
+        }
+    } catch (error) {
+        setOnline(false);
+    }
+}
+
+// Used only while the WebSocket is down, so the board keeps moving.
+function startPolling() {
+    if (pollTimer !== null) {
+        return;
+    }
+    pollTimer = setInterval(async () => {
+        try {
+            const response = await fetch("/snake/state");
+            if (response.ok) {
+                showState(await response.json());
+            }
+        } catch (error) {
+            // Keep polling until the server is back.
+        }
+    }, POLL_INTERVAL_MS);
+}
+
+function stopPolling() {
+    clearInterval(pollTimer);
+    pollTimer = null;
+}
+
+function connectLiveBoard(retryDelay) {
+    const protocol = location.protocol === "https:" ? "wss:" : "ws:";
+    const socket = new WebSocket(`${protocol}//${location.host}/snake/ws`);
+    socket.onopen = () => {
+        retryDelay = 500;
+        stopPolling();
+        setOnline(true);
+    };
+    socket.onmessage = (message) => {
+        const { state: nextState, votes } = JSON.parse(message.data);
+        showState(nextState);
+        // Blind voting hides the votes of the running tick.
+        if (votes) {
+            showVotes(votes);
+        }
+        refreshVotes();
+    };
+    socket.onclose = () => {
+        setOnline(false);
+        startPolling();
+        setTimeout(() => connectLiveBoard(Math.min(retryDelay * 2, 10000)), retryDelay);
+    };
+}
+
+function listenForGameOver() {
+    const events = new EventSource("/snake/events");
+    events.addEventListener("game_over", (event) => {
+        const result = JSON.parse(event.data);
+        document.getElementById("game-over-reason").textContent = REASONS[result.reason] || result.reason;
+        document.getElementById("game-over-score").textContent =
+            `Score ${result.score}, length ${result.final_length}, ${result.ticks} ticks`;
+        gameOver.hidden = false;
+        clearTimeout(gameOverTimer);
+        gameOverTimer = setTimeout(() => {
+            gameOver.hidden = true;
+        }, GAME_OVER_BANNER_MS);
+    });
+}
+
+document.addEventListener("keydown", (event) => {
+    const direction = KEYS[event.key];
+    if (direction) {
+        event.preventDefault();
+        vote(direction);
+    }
+});
+document.querySelectorAll(".vote").forEach((button) => {
+    button.addEventListener("click", () => vote(button.dataset.direction));
+});
+
+setInterval(updateCountdown, 100);
+setInterval(refreshVotes, VOTES_POLL_INTERVAL_MS);
+refreshVotes();
+connectLiveBoard(500);
+listenForGameOver();